@@ -1,71 +1,656 @@
-use reqwest::RequestBuilder;
+use std::{ collections::VecDeque, sync::{ Arc, Mutex }, time::Duration };
+
+use futures::{ Stream, StreamExt };
+use reqwest::{ header::{ HeaderMap, HeaderName, HeaderValue, ACCEPT }, Method, RequestBuilder };
+use serde::de::DeserializeOwned;
 
 use crate::{
-    brave::{ query_builders::web_search_query_builder, BraveClientError },
-    types::{ query_params::WebSearchQueryParamsBuilder, WebSearchQueryParams },
+    brave::{
+        cache::{ cache_key, CacheMetadata, CacheMode, CacheStatus, SearchCache },
+        coalesce::Coalescer,
+        client_builder::{ BraveClientBuilder, DEFAULT_BASE_URL },
+        query_builders::{
+            image_search_query_builder,
+            local_descriptions_query_builder,
+            local_pois_query_builder,
+            news_search_query_builder,
+            rich_query_builder,
+            spellcheck_query_builder,
+            suggest_query_builder,
+            summarizer_query_builder,
+            video_search_query_builder,
+            web_search_query_builder,
+        },
+        key_pool::{ ApiKeyPool, KeyStats },
+        rate_limiter::RateLimiter,
+        retry::{ retry_after, RetryPolicy },
+        sse::SseDecoder,
+        stats::{ ClientStats, StatsCounters },
+        BraveClientError,
+    },
+    types::{
+        answers::{ AnswerChunk, AnswerContentParser },
+        context::SearchContext,
+        query_params::ApplyDefaults,
+        AnswerDelta,
+        AnswerRequest,
+        AnswerResponse,
+        ImageSearchApiResponse,
+        LocalDescriptionsSearchApiResponse,
+        LocalPoiSearchApiResponse,
+        ImageSearchQueryParams,
+        NewsSearchApiResponse,
+        NewsSearchQueryParams,
+        PageOptions,
+        QueryDefaults,
+        RateLimitInfo,
+        RichSearchApiResponse,
+        SpellcheckApiResponse,
+        SpellcheckQueryParams,
+        SuggestApiResponse,
+        SuggestQueryParams,
+        Summary,
+        VideoSearchApiResponse,
+        VideoSearchQueryParams,
+        WebSearchQueryParams,
+    },
+    types::response::SearchResult,
+    types::summarizer::SummaryStatus,
     WebSearchApiResponse,
 };
+
+const SUMMARY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const SUMMARY_MAX_POLLS: u32 = 20;
+const LOCAL_IDS_PER_REQUEST: usize = 20;
+
+#[derive(Clone)]
 pub struct BraveClient {
-    api_key: String,
-    base_url: String,
-    client: reqwest::Client,
+    pub(crate) keys: Arc<ApiKeyPool>,
+    pub(crate) base_url: String,
+    pub(crate) client: reqwest::Client,
+    pub(crate) context: SearchContext,
+    pub(crate) default_headers: HeaderMap,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) defaults: QueryDefaults,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) rate_limiter: Option<Arc<RateLimiter>>,
+    pub(crate) rate_limit: Arc<Mutex<Option<RateLimitInfo>>>,
+    pub(crate) cache: Option<Arc<dyn SearchCache>>,
+    pub(crate) cache_mode: CacheMode,
+    pub(crate) coalescer: Option<Arc<Coalescer>>,
+    pub(crate) stats: Arc<StatsCounters>,
 }
 
 impl BraveClient {
     pub fn new(api_key: &str) -> Self {
         BraveClient {
-            api_key: api_key.to_string(),
-            base_url: String::from("https://api.search.brave.com/res/v1"),
+            keys: Arc::new(ApiKeyPool::single(api_key)),
+            base_url: String::from(DEFAULT_BASE_URL),
             client: reqwest::Client::new(),
+            context: SearchContext::default(),
+            default_headers: HeaderMap::new(),
+            timeout: None,
+            defaults: QueryDefaults::default(),
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: None,
+            rate_limit: Arc::default(),
+            cache: None,
+            cache_mode: CacheMode::default(),
+            coalescer: None,
+            stats: Arc::default(),
         }
     }
 
+    pub fn builder(api_key: &str) -> BraveClientBuilder {
+        BraveClientBuilder::new(api_key)
+    }
+
+    /// The rate limit state reported by the most recent response, shared by clones of this
+    /// client.
+    pub fn rate_limit_info(&self) -> Option<RateLimitInfo> {
+        self.rate_limit.lock().ok().and_then(|rate_limit| rate_limit.clone())
+    }
+
+    /// Request, cache hit and coalescing counters, shared by clones of this client.
+    pub fn stats(&self) -> ClientStats {
+        self.stats.snapshot()
+    }
+
+    /// Request counts, failures and health of each API key.
+    pub fn key_stats(&self) -> Vec<KeyStats> {
+        self.keys.stats()
+    }
+
+    /// Sets the location and client headers sent with every request.
+    pub fn set_context(&mut self, context: SearchContext) -> Result<(), BraveClientError> {
+        context.validate()?;
+        self.context = context;
+        Ok(())
+    }
+
+    /// Returns a client that sends `context` with its requests, falling back to this client's
+    /// default context for unset fields.
+    pub fn with_context(&self, context: &SearchContext) -> Result<BraveClient, BraveClientError> {
+        context.validate()?;
+        Ok(BraveClient { context: context.or(&self.context), ..self.clone() })
+    }
+
+    /// Returns a client whose requests use the cache according to `cache_mode`, e.g. to bypass
+    /// or refresh the cache for a single request.
+    pub fn with_cache_mode(&self, cache_mode: CacheMode) -> BraveClient {
+        BraveClient { cache_mode, ..self.clone() }
+    }
+
     pub async fn web_search(
         &self,
         query_params: &WebSearchQueryParams
     ) -> Result<WebSearchApiResponse, BraveClientError> {
-        let query = web_search_query_builder(query_params).unwrap();
-        let response = self.get_request_builder(&query).send().await?.error_for_status()?;
-
-        let result = response.json::<WebSearchApiResponse>().await?;
-        Ok(result)
+        query_params.validate()?;
+        let query_params = self.with_defaults(query_params);
+        let query: String = web_search_query_builder(&query_params)?.unwrap_or_default();
+        let mut response: WebSearchApiResponse = self.get_cached_json(&query).await?;
+        response.result_filter = query_params.result_filter;
+        Ok(response)
     }
 
     pub async fn web_search_by_query(
         &self,
         query: &str
     ) -> Result<WebSearchApiResponse, BraveClientError> {
-        let params = WebSearchQueryParamsBuilder::default().q(query).build().unwrap();
-        let query: String = params.into();
-        let response = self.get_request_builder(&query).send().await?;
-        let json = response.text().await.unwrap();
-        let result = serde_json::from_str::<WebSearchApiResponse>(&json);
-        match result {
-            Ok(resp) => {
-                return Ok(resp);
+        let params = WebSearchQueryParams { q: Some(query.to_string()), ..Default::default() };
+        self.web_search(&params).await
+    }
+
+    /// Streams the pages of a web search, starting at the params' offset. The stream ends when
+    /// the API reports no more results, at the maximum offset, after `options.max_pages` pages
+    /// or after the first error.
+    pub fn web_search_pages(
+        &self,
+        query_params: &WebSearchQueryParams,
+        options: PageOptions
+    ) -> impl Stream<Item = Result<WebSearchApiResponse, BraveClientError>> + use<> {
+        let state = (self.clone(), Some(query_params.clone()), 0);
+        futures::stream::unfold(state, move |(client, query_params, fetched)| async move {
+            let query_params = query_params?;
+            if options.max_pages.is_some_and(|max_pages| fetched >= max_pages) {
+                return None;
+            }
+            if fetched > 0 && let Some(delay) = options.delay {
+                tokio::time::sleep(delay).await;
+            }
+            match client.web_search(&query_params).await {
+                Ok(page) => {
+                    let next = query_params.next_page(&page);
+                    Some((Ok(page), (client, next, fetched + 1)))
+                }
+                Err(err) => Some((Err(err), (client, None, fetched + 1))),
+            }
+        })
+    }
+
+    /// Like `web_search_pages`, flattened into the web results of each page.
+    pub fn web_search_results(
+        &self,
+        query_params: &WebSearchQueryParams,
+        options: PageOptions
+    ) -> impl Stream<Item = Result<SearchResult, BraveClientError>> + use<> {
+        self.web_search_pages(query_params, options).flat_map(|page| {
+            let results: Vec<_> = match page {
+                Ok(page) => {
+                    page.web.map(|web| web.results).unwrap_or_default().into_iter().map(Ok).collect()
+                }
+                Err(err) => vec![Err(err)],
+            };
+            futures::stream::iter(results)
+        })
+    }
+
+    pub async fn image_search(
+        &self,
+        query_params: &ImageSearchQueryParams
+    ) -> Result<ImageSearchApiResponse, BraveClientError> {
+        query_params.validate()?;
+        let query = image_search_query_builder(&self.with_defaults(query_params))?.ok_or_else(||
+            BraveClientError::ClientError("Image search requires at least one parameter".into())
+        )?;
+        self.get_cached_json(&query).await
+    }
+
+    pub async fn news_search(
+        &self,
+        query_params: &NewsSearchQueryParams
+    ) -> Result<NewsSearchApiResponse, BraveClientError> {
+        query_params.validate()?;
+        let query = news_search_query_builder(&self.with_defaults(query_params))?.ok_or_else(||
+            BraveClientError::ClientError("News search requires at least one parameter".into())
+        )?;
+        self.get_cached_json(&query).await
+    }
+
+    pub async fn video_search(
+        &self,
+        query_params: &VideoSearchQueryParams
+    ) -> Result<VideoSearchApiResponse, BraveClientError> {
+        query_params.validate()?;
+        let query = video_search_query_builder(&self.with_defaults(query_params))?.ok_or_else(||
+            BraveClientError::ClientError("Video search requires at least one parameter".into())
+        )?;
+        self.get_cached_json(&query).await
+    }
+
+    pub async fn suggest(
+        &self,
+        query_params: &SuggestQueryParams
+    ) -> Result<SuggestApiResponse, BraveClientError> {
+        let query = suggest_query_builder(&self.with_defaults(query_params))?.ok_or_else(||
+            BraveClientError::ClientError("Suggest requires at least one parameter".into())
+        )?;
+        self.get_cached_json(&query).await
+    }
+
+    pub async fn spellcheck(
+        &self,
+        query_params: &SpellcheckQueryParams
+    ) -> Result<SpellcheckApiResponse, BraveClientError> {
+        let query = spellcheck_query_builder(&self.with_defaults(query_params))?.ok_or_else(||
+            BraveClientError::ClientError("Spellcheck requires at least one parameter".into())
+        )?;
+        self.get_cached_json(&query).await
+    }
+
+    /// Fetches the summary for a `summarizer.key` returned by a web search with `summary` set,
+    /// polling until Brave reports the summary as complete.
+    pub async fn summarize(&self, key: &str) -> Result<Summary, BraveClientError> {
+        let query = summarizer_query_builder(key);
+        for _ in 0..SUMMARY_MAX_POLLS {
+            let summary: Summary = self.get_json(&query).await?;
+            match summary.status {
+                SummaryStatus::Complete => return Ok(summary),
+                SummaryStatus::Failed => {
+                    return Err(BraveClientError::SummaryUnavailable("summarizer failed".into()));
+                }
+                SummaryStatus::Pending => tokio::time::sleep(SUMMARY_POLL_INTERVAL).await,
+            }
+        }
+        Err(
+            BraveClientError::SummaryUnavailable(
+                format!("summary not complete after {} polls", SUMMARY_MAX_POLLS)
+            )
+        )
+    }
+
+    /// Fetches points of interest for location ids from web search `locations` results.
+    /// Ids beyond the API's per-request limit are fetched in additional requests.
+    pub async fn local_pois(
+        &self,
+        ids: &[String]
+    ) -> Result<LocalPoiSearchApiResponse, BraveClientError> {
+        if ids.is_empty() {
+            return Err(BraveClientError::ClientError("Local POIs require at least one id".into()));
+        }
+        let mut response = LocalPoiSearchApiResponse {
+            result_type: String::from("local_pois"),
+            results: Vec::with_capacity(ids.len()),
+            // Merging keeps a hit only if every batch is one.
+            cache: CacheStatus::Hit { age: Duration::ZERO },
+        };
+        for batch in ids.chunks(LOCAL_IDS_PER_REQUEST) {
+            let query = local_pois_query_builder(batch);
+            let page: LocalPoiSearchApiResponse = self.get_cached_json(&query).await?;
+            response.cache = response.cache.merge(page.cache);
+            response.results.extend(page.results);
+        }
+        Ok(response)
+    }
+
+    pub async fn local_descriptions(
+        &self,
+        ids: &[String]
+    ) -> Result<LocalDescriptionsSearchApiResponse, BraveClientError> {
+        if ids.is_empty() {
+            return Err(
+                BraveClientError::ClientError("Local descriptions require at least one id".into())
+            );
+        }
+        let mut response = LocalDescriptionsSearchApiResponse {
+            result_type: String::from("local_descriptions"),
+            results: Vec::with_capacity(ids.len()),
+            cache: CacheStatus::Hit { age: Duration::ZERO },
+        };
+        for batch in ids.chunks(LOCAL_IDS_PER_REQUEST) {
+            let query = local_descriptions_query_builder(batch);
+            let page: LocalDescriptionsSearchApiResponse = self.get_cached_json(&query).await?;
+            response.cache = response.cache.merge(page.cache);
+            response.results.extend(page.results);
+        }
+        Ok(response)
+    }
+
+    /// Fetches the rich result (weather, stocks, sports, ...) for the `rich.hint.callback_key`
+    /// returned by a web search with `enable_rich_callback` set.
+    pub async fn rich_result(
+        &self,
+        callback_key: &str
+    ) -> Result<RichSearchApiResponse, BraveClientError> {
+        self.get_cached_json(&rich_query_builder(callback_key)).await
+    }
+
+    /// Sends a chat completion to the AI grounding endpoint and waits for the full answer.
+    pub async fn answer(
+        &self,
+        request: &AnswerRequest
+    ) -> Result<AnswerResponse, BraveClientError> {
+        let request = AnswerRequest { stream: false, ..request.clone() };
+        let request_builder = self
+            .request_builder(Method::POST, "/chat/completions", "application/json")
+            .json(&request);
+        self.send_json(request_builder).await
+    }
+
+    /// Streams a chat completion from the AI grounding endpoint as typed deltas parsed from the
+    /// server-sent events. The stream ends at the `[DONE]` event.
+    pub async fn answer_stream(
+        &self,
+        request: &AnswerRequest
+    ) -> Result<impl Stream<Item = Result<AnswerDelta, BraveClientError>>, BraveClientError> {
+        let request = AnswerRequest { stream: true, ..request.clone() };
+        let request_builder = self
+            .request_builder(Method::POST, "/chat/completions", "text/event-stream")
+            .json(&request);
+        let response = self.send(request_builder).await?;
+        Ok(answer_deltas(response))
+    }
+
+    fn with_defaults<T: ApplyDefaults + Clone>(&self, query_params: &T) -> T {
+        let mut query_params = query_params.clone();
+        query_params.apply_defaults(&self.defaults);
+        query_params
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, url_path: &str) -> Result<T, BraveClientError> {
+        self.send_json(self.get_request_builder(url_path)).await
+    }
+
+    /// Like `get_json`, but serves and stores responses through the client's cache and
+    /// coalesces identical concurrent requests.
+    async fn get_cached_json<T: DeserializeOwned + CacheMetadata>(
+        &self,
+        url_path: &str
+    ) -> Result<T, BraveClientError> {
+        let key = cache_key(&self.base_url, url_path, &self.context.headers());
+        // A `Cache-Control: no-cache` request asks for a fresh response, so it skips the
+        // cached entry but still stores the new one.
+        let cache_mode = match self.cache_mode {
+            CacheMode::Use if self.context.no_cache == Some(true) => CacheMode::Refresh,
+            cache_mode => cache_mode,
+        };
+        let cache = self.cache.as_ref().filter(|_| cache_mode != CacheMode::Bypass);
+        let Some(cache) = cache else {
+            return parse_json(&self.get_text(url_path, key).await?);
+        };
+        if cache_mode == CacheMode::Use && let Some(cached) = cache.get(&key) {
+            let mut response: T = parse_json(&cached.body)?;
+            let age = cached.stored_at.elapsed().unwrap_or_default();
+            response.set_cache_status(CacheStatus::Hit { age });
+            self.stats.record_cache_hit();
+            return Ok(response);
+        }
+        let json = self.get_text(url_path, key.clone()).await?;
+        let mut response: T = parse_json(&json)?;
+        cache.put(&key, &json);
+        response.set_cache_status(match cache_mode {
+            CacheMode::Refresh => CacheStatus::Refreshed,
+            _ => CacheStatus::Miss,
+        });
+        Ok(response)
+    }
+
+    /// Fetches the response body, sharing the request with identical in-flight calls when
+    /// coalescing is enabled.
+    async fn get_text(&self, url_path: &str, key: String) -> Result<String, BraveClientError> {
+        let fetch = async {
+            Ok(self.send(self.get_request_builder(url_path)).await?.text().await?)
+        };
+        let Some(coalescer) = &self.coalescer else {
+            return fetch.await;
+        };
+        let (result, shared) = coalescer.run(key, fetch).await;
+        if shared {
+            self.stats.record_coalesced();
+        }
+        result
+    }
+
+    async fn send_json<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder
+    ) -> Result<T, BraveClientError> {
+        let response = self.send(request).await?;
+        parse_json(&response.text().await?)
+    }
+
+    /// Sends the request, retrying retryable failures according to the client's retry policy.
+    /// Responses rejecting or rate limiting a key are first retried with the pool's other keys.
+    async fn send(&self, request: RequestBuilder) -> Result<reqwest::Response, BraveClientError> {
+        let policy = &self.retry_policy;
+        let mut attempt = 1;
+        let mut failovers = 0;
+        loop {
+            let Some(current) = request.try_clone() else {
+                return Ok(self.send_attempt(request).await??.error_for_status()?);
+            };
+            let can_retry = attempt < policy.max_attempts;
+            let delay = match self.send_attempt(current).await? {
+                Ok(response) if
+                    is_key_rejection(response.status()) &&
+                    failovers + 1 < self.keys.len() &&
+                    self.keys.has_available()
+                => {
+                    failovers += 1;
+                    continue;
+                }
+                Ok(response) if can_retry && policy.is_retryable_status(response.status()) => {
+                    match policy.response_delay(attempt, response.status(), response.headers()) {
+                        Some(delay) => delay,
+                        None => {
+                            return Ok(response.error_for_status()?);
+                        }
+                    }
+                }
+                Ok(response) => {
+                    return Ok(response.error_for_status()?);
+                }
+                Err(err) if can_retry && policy.is_retryable_error(&err) => {
+                    policy.error_delay(attempt)
+                }
+                Err(err) => {
+                    return Err(err.into());
+                }
+            };
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Sends a single attempt with the next key from the pool, recording its rate limits and
+    /// the key's usage.
+    async fn send_attempt(
+        &self,
+        request: RequestBuilder
+    ) -> Result<reqwest::Result<reqwest::Response>, BraveClientError> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await?;
+        }
+        let (index, api_key) = self.keys.select();
+        self.stats.record_request();
+        let result = request.header("X-Subscription-Token", api_key).send().await;
+        match &result {
+            Ok(response) => {
+                let info = RateLimitInfo::from_headers(response.headers());
+                let retry_after = retry_after(response.headers());
+                self.keys.record_response(index, response.status(), retry_after, info.as_ref());
+                if let Some(info) = info {
+                    self.record_rate_limit(info).await;
+                }
             }
-            Err(err) => Err(BraveClientError::ResponseDeserializationError(err.to_string())),
+            Err(_) => self.keys.record_error(index),
+        }
+        Ok(result)
+    }
+
+    async fn record_rate_limit(&self, info: RateLimitInfo) {
+        // Each key of a pool reports its own quota, which the pool tracks per key. Feeding them
+        // into the shared limiter would let one exhausted key throttle requests to the others.
+        if let Some(rate_limiter) = &self.rate_limiter && self.keys.len() == 1 {
+            rate_limiter.observe(&info).await;
+        }
+        if let Ok(mut rate_limit) = self.rate_limit.lock() {
+            *rate_limit = Some(info);
         }
     }
 
     fn get_request_builder(&self, url_path: &str) -> RequestBuilder {
+        self.request_builder(Method::GET, url_path, "application/json")
+    }
+
+    fn request_builder(
+        &self,
+        method: Method,
+        url_path: &str,
+        accept: &'static str
+    ) -> RequestBuilder {
         let mut url = self.base_url.to_owned();
         url.push_str(url_path);
-        let response = self.client
-            .get(url)
-            .header("X-Subscription-Token", &self.api_key)
-            .header("Accept", "application/json");
-        return response;
+        let mut headers = self.default_headers.clone();
+        for (name, value) in self.context.headers() {
+            if let (Ok(name), Ok(value)) =
+                (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(&value))
+            {
+                headers.insert(name, value);
+            }
+        }
+        headers.insert(ACCEPT, HeaderValue::from_static(accept));
+        let request_builder = self.client.request(method, url).headers(headers);
+        match self.timeout {
+            Some(timeout) => request_builder.timeout(timeout),
+            None => request_builder,
+        }
     }
 }
 
+fn parse_json<T: DeserializeOwned>(json: &str) -> Result<T, BraveClientError> {
+    serde_json::from_str::<T>(json).map_err(|err| {
+        BraveClientError::ResponseDeserializationError(err.to_string())
+    })
+}
+
+fn is_key_rejection(status: reqwest::StatusCode) -> bool {
+    matches!(status.as_u16(), 401 | 403 | 429)
+}
+
+fn answer_deltas(
+    response: reqwest::Response
+) -> impl Stream<Item = Result<AnswerDelta, BraveClientError>> {
+    let state = (
+        response.bytes_stream(),
+        SseDecoder::default(),
+        AnswerContentParser::default(),
+        VecDeque::new(),
+        false,
+    );
+    futures::stream::unfold(state, |state| async move {
+        let (mut bytes, mut decoder, mut parser, mut pending, mut done) = state;
+        loop {
+            if let Some(delta) = pending.pop_front() {
+                return Some((delta, (bytes, decoder, parser, pending, done)));
+            }
+            if done {
+                return None;
+            }
+            let events = match bytes.next().await {
+                Some(Ok(chunk)) => decoder.push(&chunk),
+                Some(Err(err)) => {
+                    pending.push_back(Err(err.into()));
+                    done = true;
+                    continue;
+                }
+                None => {
+                    done = true;
+                    decoder.finish().into_iter().collect()
+                }
+            };
+            for data in events {
+                if data == "[DONE]" {
+                    done = true;
+                    break;
+                }
+                match serde_json::from_str::<AnswerChunk>(&data) {
+                    Ok(chunk) => {
+                        pending.extend(chunk.parse_deltas(&mut parser).into_iter().map(Ok));
+                    }
+                    Err(err) => {
+                        let err = BraveClientError::ResponseDeserializationError(err.to_string());
+                        pending.push_back(Err(err));
+                    }
+                }
+            }
+            if done {
+                pending.extend(parser.finish().into_iter().map(Ok));
+            }
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::rich::RichResult;
+    use crate::types::{ Country, Date, Freshness, ResultSection, SafeSearch, SearchLang, Section };
+    use crate::types::context::{ LocationHintsBuilder, SearchContextBuilder };
+    use crate::types::query_params::{
+        WebSearchQueryParamsBuilder,
+        ImageSearchQueryParamsBuilder,
+        NewsSearchQueryParamsBuilder,
+        SpellcheckQueryParamsBuilder,
+        SuggestQueryParamsBuilder,
+        VideoSearchQueryParamsBuilder,
+    };
+    use crate::brave::{
+        ApiKeyPool,
+        MemoryCache,
+        RateLimiter,
+        RetryPolicyBuilder,
+        RotationStrategy,
+    };
     use httpmock::MockServer;
-    use httpmock::Method::GET;
+    use httpmock::prelude::HttpMockRequest;
+    use std::sync::atomic::{ AtomicUsize, Ordering };
+    use httpmock::Method::{ GET, POST };
+    use crate::types::answers::{ AnswerRequestBuilder, ChatMessage, Citation, Usage };
     use serde_json::json;
 
+    /// A web search response for "rust" with every required `query` field. Top-level fields of
+    /// `extra` are added to the response, and fields of `extra["query"]` override the query's.
+    fn web_search_body(extra: serde_json::Value) -> serde_json::Value {
+        let mut body = json!({
+            "type": "search",
+            "query": { "original": "rust", "show_strict_warning": false, "is_navigational": false, "is_news_breaking": false, "spellcheck_off": false, "country": "us", "bad_results": false, "should_fallback": false, "postal_code": "", "city": "", "header_country": "", "more_results_available": false, "state": "" }
+        });
+        if let serde_json::Value::Object(extra) = extra {
+            for (name, value) in extra {
+                match (name.as_str(), value) {
+                    ("query", serde_json::Value::Object(query)) => {
+                        body["query"].as_object_mut().unwrap().extend(query);
+                    }
+                    (_, value) => {
+                        body[name] = value;
+                    }
+                }
+            }
+        }
+        body
+    }
+
     #[tokio::test]
     async fn test_web_search_by_query() {
         let server = MockServer::start();
@@ -91,9 +676,20 @@ mod tests {
         assert!(result.is_ok());
         let response = result.unwrap();
         assert_eq!(response.result_type, "web");
+        assert_eq!(response.query.country(), Some(Country::Us));
         mock.assert();
     }
 
+    #[test]
+    fn test_query_accepts_countries_outside_the_catalog() {
+        let body = web_search_body(json!({ "query": { "country": "ie" } }));
+        let query: crate::types::response::Query = serde_json::from_value(body["query"].clone())
+            .unwrap();
+
+        assert_eq!(query.country, "ie");
+        assert_eq!(query.country(), None);
+    }
+
     #[tokio::test]
     async fn test_web_search_returns_brave_client_error_on_http_error() {
         let server = MockServer::start();
@@ -188,6 +784,7 @@ mod tests {
         assert!(result.is_ok());
         let response = result.unwrap();
         assert_eq!(response.result_type, "videos");
+        assert!(!response.mutated_by_goggles());
         let videos = response.videos.unwrap();
         let video_result = videos.results.unwrap();
 
@@ -263,4 +860,1316 @@ mod tests {
         assert!(mixed_main[0].all);
         mock.assert();
     }
+
+    #[tokio::test]
+    async fn test_image_search() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/images/search")
+                .query_param("q", "ferris crab")
+                .query_param("count", "100")
+                .query_param("spellcheck", "0")
+                .query_param("safesearch", "strict");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    serde_json::json!({
+                        "type": "images",
+                        "query": { "original": "ferris crab", "spellcheck_off": true },
+                        "results": [
+                            {
+                                "type": "image_result",
+                                "title": "Ferris the crab",
+                                "url": "https://rustacean.net/",
+                                "source": "rustacean.net",
+                                "page_fetched": "2025-08-01T10:00:00Z",
+                                "thumbnail": {
+                                    "src": "https://imgs.search.brave.com/ferris-thumb",
+                                    "width": 500,
+                                    "height": 333
+                                },
+                                "properties": {
+                                    "url": "https://rustacean.net/assets/rustacean-flat-happy.png",
+                                    "placeholder": "https://imgs.search.brave.com/ferris-placeholder",
+                                    "width": 1200,
+                                    "height": 800
+                                },
+                                "meta_url": {
+                                    "scheme": "https",
+                                    "netloc": "rustacean.net",
+                                    "hostname": "rustacean.net",
+                                    "favicon": "https://imgs.search.brave.com/favicon",
+                                    "path": ""
+                                },
+                                "confidence": "high"
+                            }
+                        ],
+                        "extra": { "might_be_offensive": false }
+                    })
+                );
+        });
+        let mut client = BraveClient::new("test_key");
+        client.base_url = server.base_url();
+        let params = ImageSearchQueryParamsBuilder::default()
+            .q("ferris crab")
+            .count(100u32)
+            .spellcheck(false)
+            .safesearch(SafeSearch::Strict)
+            .build()
+            .unwrap();
+        let moderate = ImageSearchQueryParams { safesearch: Some(SafeSearch::Moderate), ..params.clone() };
+
+        let response = client.image_search(&params).await.unwrap();
+        let rejected = client.image_search(&moderate).await;
+
+        assert_eq!(response.result_type, "images");
+        assert_eq!(response.results.len(), 1);
+        let image = &response.results[0];
+        assert_eq!(image.url, "https://rustacean.net/");
+        assert_eq!(image.properties.url, "https://rustacean.net/assets/rustacean-flat-happy.png");
+        assert_eq!(image.properties.width, Some(1200));
+        assert_eq!(image.properties.height, Some(800));
+        assert_eq!(image.thumbnail.width, Some(500));
+        assert_eq!(image.meta_url.as_ref().unwrap().hostname, "rustacean.net");
+        assert!(matches!(rejected, Err(BraveClientError::InvalidQuery(_))));
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_news_search() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/news/search")
+                .query_param("q", "rust release")
+                .query_param("freshness", "pd")
+                .query_param("extra_snippets", "true")
+                .query_param("goggles", "https://example.com/tech.goggle");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    serde_json::json!({
+                        "type": "news",
+                        "query": { "original": "rust release" },
+                        "results": [
+                            {
+                                "type": "news_result",
+                                "url": "https://blog.rust-lang.org/2025/08/07/Rust-1.89.0/",
+                                "title": "Announcing Rust 1.89.0",
+                                "description": "The Rust team is happy to announce a new version of Rust.",
+                                "age": "2 hours ago",
+                                "page_age": "2025-08-07T12:00:00",
+                                "breaking": true,
+                                "meta_url": {
+                                    "scheme": "https",
+                                    "netloc": "blog.rust-lang.org",
+                                    "hostname": "blog.rust-lang.org",
+                                    "favicon": "https://imgs.search.brave.com/favicon",
+                                    "path": "› 2025 › 08 › 07"
+                                },
+                                "thumbnail": { "src": "https://imgs.search.brave.com/rust-thumb" },
+                                "extra_snippets": ["Rust 1.89 stabilizes explicitly inferred const arguments."]
+                            }
+                        ]
+                    })
+                );
+        });
+        let mut client = BraveClient::new("test_key");
+        client.base_url = server.base_url();
+        let params = NewsSearchQueryParamsBuilder::default()
+            .q("rust release")
+            .freshness(Freshness::PastDay)
+            .extra_snippets(true)
+            .goggles(vec!["https://example.com/tech.goggle".to_string()])
+            .build()
+            .unwrap();
+
+        let response = client.news_search(&params).await.unwrap();
+
+        assert_eq!(response.result_type, "news");
+        assert_eq!(response.results.len(), 1);
+        let news = &response.results[0];
+        assert_eq!(news.breaking, Some(true));
+        assert_eq!(news.page_age.as_deref(), Some("2025-08-07T12:00:00"));
+        assert_eq!(news.meta_url.as_ref().unwrap().netloc, "blog.rust-lang.org");
+        assert_eq!(news.thumbnail.as_ref().unwrap().src, "https://imgs.search.brave.com/rust-thumb");
+        assert_eq!(news.extra_snippets.as_ref().unwrap().len(), 1);
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_video_search_follows_more_results_available() {
+        let server = MockServer::start();
+        // Registered first so the offset request doesn't fall through to the first page mock.
+        let second_page = server.mock(|when, then| {
+            when.method(GET).path("/videos/search").query_param("q", "tokio").query_param("offset", "1");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    serde_json::json!({
+                        "type": "videos",
+                        "query": { "original": "tokio", "more_results_available": false },
+                        "results": []
+                    })
+                );
+        });
+        let first_page = server.mock(|when, then| {
+            when.method(GET).path("/videos/search").query_param("q", "tokio");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    serde_json::json!({
+                        "type": "videos",
+                        "query": { "original": "tokio", "more_results_available": true },
+                        "results": [
+                            {
+                                "type": "video_result",
+                                "url": "https://www.youtube.com/watch?v=tokio",
+                                "title": "Async Rust with Tokio",
+                                "description": "An introduction to the Tokio runtime.",
+                                "age": "March 3, 2025",
+                                "video": {
+                                    "duration": "42:17",
+                                    "views": 125000,
+                                    "creator": "Rust Conf",
+                                    "publisher": "YouTube",
+                                    "thumbnail": { "src": "https://imgs.search.brave.com/tokio-video" },
+                                    "tags": ["rust", "async"],
+                                    "author": { "name": "Rust Conf", "url": "https://www.youtube.com/@rustconf" },
+                                    "requires_subscription": false
+                                }
+                            }
+                        ]
+                    })
+                );
+        });
+        let mut client = BraveClient::new("test_key");
+        client.base_url = server.base_url();
+        let params = VideoSearchQueryParamsBuilder::default().q("tokio").build().unwrap();
+
+        let response = client.video_search(&params).await.unwrap();
+
+        let video = response.results[0].video.as_ref().unwrap();
+        assert_eq!(video.duration.as_deref(), Some("42:17"));
+        assert_eq!(video.views, Some(125000));
+        assert_eq!(video.creator.as_deref(), Some("Rust Conf"));
+        assert_eq!(video.publisher.as_deref(), Some("YouTube"));
+        assert_eq!(video.tags.as_ref().unwrap(), &vec!["rust".to_string(), "async".to_string()]);
+        assert_eq!(video.requires_subscription, Some(false));
+
+        let next = params.next_page(&response).unwrap();
+        assert_eq!(next.offset, Some(1));
+        let response = client.video_search(&next).await.unwrap();
+        assert!(response.results.is_empty());
+        assert!(next.next_page(&response).is_none());
+        first_page.assert();
+        second_page.assert();
+    }
+
+    #[tokio::test]
+    async fn test_suggest_with_rich_results() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/suggest/search")
+                .header("X-Subscription-Token", "test_key")
+                .query_param("q", "ferr")
+                .query_param("count", "2")
+                .query_param("rich", "true");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    serde_json::json!({
+                        "type": "suggest",
+                        "query": { "original": "ferr" },
+                        "results": [
+                            {
+                                "query": "ferris the crab",
+                                "is_entity": true,
+                                "title": "Ferris",
+                                "description": "Unofficial mascot of the Rust programming language",
+                                "img": "https://imgs.search.brave.com/ferris"
+                            },
+                            { "query": "ferrari", "is_entity": false }
+                        ]
+                    })
+                );
+        });
+        let mut client = BraveClient::new("test_key");
+        client.base_url = server.base_url();
+        let params = SuggestQueryParamsBuilder::default()
+            .q("ferr")
+            .count(2u32)
+            .rich(true)
+            .build()
+            .unwrap();
+
+        let response = client.suggest(&params).await.unwrap();
+
+        assert_eq!(response.query.original, "ferr");
+        assert_eq!(response.results.len(), 2);
+        let entity = &response.results[0];
+        assert_eq!(entity.query, "ferris the crab");
+        assert_eq!(entity.is_entity, Some(true));
+        assert_eq!(entity.title.as_deref(), Some("Ferris"));
+        assert!(entity.img.is_some());
+        assert_eq!(response.results[1].is_entity, Some(false));
+        assert!(response.results[1].title.is_none());
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_spellcheck() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/spellcheck/search")
+                .query_param("q", "rust borow checker")
+                .query_param("lang", "en");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    serde_json::json!({
+                        "type": "spellcheck",
+                        "query": { "original": "rust borow checker" },
+                        "results": [{ "query": "rust borrow checker" }]
+                    })
+                );
+        });
+        let mut client = BraveClient::new("test_key");
+        client.base_url = server.base_url();
+        let params = SpellcheckQueryParamsBuilder::default()
+            .q("rust borow checker")
+            .lang("en")
+            .build()
+            .unwrap();
+
+        let response = client.spellcheck(&params).await.unwrap();
+
+        assert_eq!(response.result_type, "spellcheck");
+        assert_eq!(response.query.original, "rust borow checker");
+        assert_eq!(response.results.len(), 1);
+        assert_eq!(response.results[0].query, "rust borrow checker");
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_web_search_returns_summarizer_key() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/web/search").query_param("q", "what is rust").query_param("summary", "1");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    web_search_body(json!({
+                        "query": { "original": "what is rust" },
+                        "summarizer": { "type": "summarizer", "key": "{\"query\": \"what is rust\"}" }
+                    }))
+                );
+        });
+        let mut client = BraveClient::new("test_key");
+        client.base_url = server.base_url();
+        let params = WebSearchQueryParamsBuilder::default().q("what is rust").summary(true).build().unwrap();
+
+        let response = client.web_search(&params).await.unwrap();
+
+        assert_eq!(response.summarizer.unwrap().key, "{\"query\": \"what is rust\"}");
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_summarize_polls_until_complete() {
+        let server = MockServer::start_async().await;
+        let pending = server.mock_async(|when, then| {
+            when.method(GET).path("/summarizer/search").query_param("key", "summary-key");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(serde_json::json!({ "type": "summarizer", "status": "processing" }));
+        }).await;
+        let mut client = BraveClient::new("test_key");
+        client.base_url = server.base_url();
+
+        let complete_after_first_poll = async {
+            while pending.hits_async().await < 1 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            pending.delete_async().await;
+            server.mock_async(|when, then| {
+                when.method(GET)
+                    .path("/summarizer/search")
+                    .query_param("key", "summary-key")
+                    .query_param("entity_info", "1");
+                then.status(200)
+                    .header("Content-Type", "application/json")
+                    .json_body(
+                        serde_json::json!({
+                            "type": "summarizer",
+                            "status": "complete",
+                            "title": "What is Rust?",
+                            "summary": [
+                                { "type": "token", "data": "Rust is a systems " },
+                                { "type": "token", "data": "programming language." }
+                            ],
+                            "enrichments": {
+                                "raw": "Rust is a systems programming language.",
+                                "entities": [{ "name": "Rust", "url": "https://www.rust-lang.org/" }],
+                                "context": [
+                                    { "title": "Rust Programming Language", "url": "https://www.rust-lang.org/" }
+                                ]
+                            },
+                            "followups": ["Who created Rust?"]
+                        })
+                    );
+            }).await
+        };
+        let (summary, complete) = tokio::join!(client.summarize("summary-key"), complete_after_first_poll);
+
+        let summary = summary.unwrap();
+        assert_eq!(summary.status, SummaryStatus::Complete);
+        assert_eq!(summary.message(), "Rust is a systems programming language.");
+        assert_eq!(summary.entities()[0].name, "Rust");
+        assert_eq!(summary.citations()[0].url, "https://www.rust-lang.org/");
+        assert_eq!(summary.followups(), ["Who created Rust?"]);
+        complete.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_local_pois() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/local/pois")
+                .query_param("ids", "loc-1")
+                .query_param("ids", "loc-2");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    serde_json::json!({
+                        "type": "local_pois",
+                        "results": [
+                            {
+                                "type": "location_result",
+                                "id": "loc-1",
+                                "url": "https://crabshack.example.com/",
+                                "title": "The Crab Shack",
+                                "description": "Seafood restaurant",
+                                "coordinates": [37.7749, -122.4194],
+                                "price_range": "$$",
+                                "opening_hours": {
+                                    "current_day": [
+                                        { "abbr_name": "Mon", "full_name": "Monday", "opens": "11:00", "closes": "22:00" }
+                                    ]
+                                },
+                                "pictures": {
+                                    "viewMoreUrl": "https://crabshack.example.com/photos",
+                                    "results": [{ "src": "https://imgs.search.brave.com/crab-shack" }]
+                                },
+                                "reviews": {
+                                    "results": [
+                                        {
+                                            "description": "Great crab cakes.",
+                                            "rating": { "ratingValue": 4.5, "bestRating": 5.0, "reviewCount": 1 }
+                                        }
+                                    ],
+                                    "reviews_in_foreign_language": false
+                                }
+                            },
+                            {
+                                "type": "location_result",
+                                "id": "loc-2",
+                                "url": "https://ferris-cafe.example.com/",
+                                "title": "Ferris Cafe",
+                                "description": "Coffee shop"
+                            }
+                        ]
+                    })
+                );
+        });
+        let mut client = BraveClient::new("test_key");
+        client.base_url = server.base_url();
+
+        let response = client.local_pois(&["loc-1".to_string(), "loc-2".to_string()]).await.unwrap();
+
+        assert_eq!(response.results.len(), 2);
+        let poi = &response.results[0];
+        assert_eq!(poi.id.as_deref(), Some("loc-1"));
+        assert_eq!(poi.price_range.as_deref(), Some("$$"));
+        let today = &poi.opening_hours.as_ref().unwrap().current_day.as_ref().unwrap()[0];
+        assert_eq!(today.opens, "11:00");
+        assert_eq!(poi.pictures.as_ref().unwrap().results.len(), 1);
+        let review = &poi.reviews.as_ref().unwrap().results[0];
+        assert_eq!(review.rating.as_ref().unwrap().rating_value, 4.5);
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_local_descriptions_batches_ids() {
+        let server = MockServer::start();
+        let first_batch = server.mock(|when, then| {
+            when.method(GET).path("/local/descriptions").query_param("ids", "loc-0");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    serde_json::json!({
+                        "type": "local_descriptions",
+                        "results": [{ "type": "local_description", "id": "loc-0", "description": "First" }]
+                    })
+                );
+        });
+        let second_batch = server.mock(|when, then| {
+            when.method(GET).path("/local/descriptions").query_param("ids", "loc-20");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    serde_json::json!({
+                        "type": "local_descriptions",
+                        "results": [{ "type": "local_description", "id": "loc-20", "description": "Last" }]
+                    })
+                );
+        });
+        let mut client = BraveClient::new("test_key");
+        client.base_url = server.base_url();
+        let ids: Vec<String> = (0..21).map(|i| format!("loc-{}", i)).collect();
+
+        let response = client.local_descriptions(&ids).await.unwrap();
+
+        assert_eq!(response.results.len(), 2);
+        assert_eq!(response.results[0].id, "loc-0");
+        assert_eq!(response.results[1].description.as_deref(), Some("Last"));
+        first_batch.assert();
+        second_batch.assert();
+    }
+
+    #[tokio::test]
+    async fn test_web_search_returns_rich_callback_hint() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/web/search")
+                .query_param("q", "weather in berlin")
+                .query_param("enable_rich_callback", "1");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    web_search_body(json!({
+                        "query": { "original": "weather in berlin", "country": "de" },
+                        "rich": { "type": "rich", "hint": { "vertical": "weather", "callback_key": "weather-key" } }
+                    }))
+                );
+        });
+        let mut client = BraveClient::new("test_key");
+        client.base_url = server.base_url();
+        let params = WebSearchQueryParamsBuilder::default()
+            .q("weather in berlin")
+            .enable_rich_callback(true)
+            .build()
+            .unwrap();
+
+        let response = client.web_search(&params).await.unwrap();
+
+        let hint = response.rich.unwrap().hint.unwrap();
+        assert_eq!(hint.vertical, "weather");
+        assert_eq!(hint.callback_key, "weather-key");
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_rich_result() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/web/rich").query_param("callback_key", "rich-key");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    serde_json::json!({
+                        "type": "rich",
+                        "results": [
+                            {
+                                "type": "rich",
+                                "subtype": "weather",
+                                "location": { "name": "Berlin", "country": "DE" },
+                                "current_weather": { "temp": 21.5, "description": "Sunny" }
+                            },
+                            {
+                                "type": "rich",
+                                "subtype": "basketball",
+                                "league": "NBA",
+                                "games": [{ "home_team": "Lakers", "away_team": "Celtics", "home_score": 101, "away_score": 99 }]
+                            },
+                            { "type": "rich", "subtype": "calculator", "expression": "2+2", "answer": "4" },
+                            { "type": "rich", "subtype": "translator", "text": "hallo" }
+                        ]
+                    })
+                );
+        });
+        let mut client = BraveClient::new("test_key");
+        client.base_url = server.base_url();
+
+        let response = client.rich_result("rich-key").await.unwrap();
+
+        assert_eq!(response.results.len(), 4);
+        let RichResult::Weather(weather) = &response.results[0] else {
+            panic!("Expected RichResult::Weather");
+        };
+        assert_eq!(weather.location.as_ref().unwrap().name, "Berlin");
+        assert_eq!(weather.current_weather.as_ref().unwrap().temp, Some(21.5));
+        let RichResult::Sports(sports) = &response.results[1] else {
+            panic!("Expected RichResult::Sports");
+        };
+        assert_eq!(sports.sport, "basketball");
+        assert_eq!(sports.games.as_ref().unwrap()[0].home_score, Some(101));
+        assert!(matches!(&response.results[2], RichResult::Calculator(calc) if calc.answer == "4"));
+        assert!(matches!(&response.results[3], RichResult::Unknown(_)));
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_web_search_reports_goggles_reranking() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/web/search")
+                .query_param("q", "rust")
+                .query_param("goggles", "$boost=3,site=docs.rs");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    web_search_body(json!({
+                        "web": { "type": "search", "results": [], "mutated_by_goggles": true },
+                        "news": { "type": "news", "results": [], "mutated_by_goggles": false }
+                    }))
+                );
+        });
+        let mut client = BraveClient::new("test_key");
+        client.base_url = server.base_url();
+        let params = WebSearchQueryParamsBuilder::default()
+            .q("rust")
+            .goggles(vec!["$boost=3,site=docs.rs".to_string()])
+            .build()
+            .unwrap();
+
+        let response = client.web_search(&params).await.unwrap();
+
+        assert!(response.mutated_by_goggles());
+        assert!(response.web.as_ref().unwrap().mutated_by_goggles);
+        assert!(!response.news.as_ref().unwrap().mutated_by_goggles);
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_answer() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/chat/completions")
+                .header("X-Subscription-Token", "test_key")
+                .json_body(
+                    serde_json::json!({
+                        "model": "brave",
+                        "messages": [{ "role": "user", "content": "What is Rust?" }],
+                        "stream": false,
+                        "enable_citations": true
+                    })
+                );
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    serde_json::json!({
+                        "id": "chatcmpl-1",
+                        "object": "chat.completion",
+                        "created": 1755933539,
+                        "model": "brave",
+                        "choices": [
+                            {
+                                "index": 0,
+                                "message": {
+                                    "role": "assistant",
+                                    "content": "Rust is a systems language.<citation>{\"number\": 1, \"url\": \"https://www.rust-lang.org/\"}</citation>"
+                                },
+                                "finish_reason": "stop"
+                            }
+                        ],
+                        "usage": { "prompt_tokens": 12, "completion_tokens": 8, "total_tokens": 20 }
+                    })
+                );
+        });
+        let mut client = BraveClient::new("test_key");
+        client.base_url = server.base_url();
+        let request = AnswerRequestBuilder::default()
+            .messages(vec![ChatMessage::user("What is Rust?")])
+            .enable_citations(true)
+            .build()
+            .unwrap();
+
+        let response = client.answer(&request).await.unwrap();
+
+        assert_eq!(response.text(), "Rust is a systems language.");
+        let citations = response.citations();
+        assert_eq!(citations.len(), 1);
+        assert_eq!(citations[0].number, Some(1));
+        assert_eq!(citations[0].url, "https://www.rust-lang.org/");
+        assert_eq!(response.usage.unwrap().total_tokens, Some(20));
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_answer_stream() {
+        let server = MockServer::start();
+        let events = [
+            r#"{"id":"chatcmpl-1","choices":[{"index":0,"delta":{"role":"assistant","content":"Rust is "}}]}"#,
+            r#"{"id":"chatcmpl-1","choices":[{"index":0,"delta":{"content":"fast."}}]}"#,
+            r#"{"id":"chatcmpl-1","choices":[{"index":0,"delta":{"content":"<citation>{\"number\":1,\"url\":\"https://www.rust-lang.org/\"}</citation>"}}]}"#,
+            r#"{"id":"chatcmpl-1","choices":[{"index":0,"delta":{"content":"<usage>{\"X-Request-Tokens-In\":12,\"X-Request-Tokens-Out\":3}</usage>"},"finish_reason":"stop"}]}"#,
+        ];
+        let body = events
+            .iter()
+            .map(|event| format!("data: {}\n\n", event))
+            .chain(std::iter::once("data: [DONE]\n\n".to_string()))
+            .collect::<String>();
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/chat/completions")
+                .json_body_partial(r#"{ "stream": true }"#);
+            then.status(200).header("Content-Type", "text/event-stream").body(body);
+        });
+        let mut client = BraveClient::new("test_key");
+        client.base_url = server.base_url();
+        let request = AnswerRequestBuilder::default()
+            .messages(vec![ChatMessage::user("Is Rust fast?")])
+            .build()
+            .unwrap();
+
+        let stream = client.answer_stream(&request).await.unwrap();
+        let deltas: Vec<AnswerDelta> = stream
+            .map(|delta| delta.unwrap())
+            .collect().await;
+
+        assert_eq!(
+            deltas,
+            vec![
+                AnswerDelta::Text("Rust is ".to_string()),
+                AnswerDelta::Text("fast.".to_string()),
+                AnswerDelta::Citation(Citation {
+                    number: Some(1),
+                    url: "https://www.rust-lang.org/".to_string(),
+                    favicon: None,
+                    snippet: None,
+                    start_index: None,
+                    end_index: None,
+                }),
+                AnswerDelta::Usage(Usage {
+                    prompt_tokens: Some(12),
+                    completion_tokens: Some(3),
+                    total_tokens: None,
+                })
+            ]
+        );
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_answer_stream_reassembles_split_tags() {
+        let server = MockServer::start();
+        let events = [
+            r#"{"id":"chatcmpl-1","choices":[{"index":0,"delta":{"content":"Fast.<cita"}}]}"#,
+            r#"{"id":"chatcmpl-1","choices":[{"index":0,"delta":{"content":"tion>{\"url\":"}}]}"#,
+            r#"{"id":"chatcmpl-1","choices":[{"index":0,"delta":{"content":"\"https://www.rust-lang.org/\"}</citation> Done"}}]}"#,
+        ];
+        // The last event is not followed by a blank line.
+        let body = events.map(|event| format!("data: {}", event)).join("\n\n");
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/chat/completions").header("Accept", "text/event-stream");
+            then.status(200).header("Content-Type", "text/event-stream").body(body);
+        });
+        let mut client = BraveClient::new("test_key");
+        client.base_url = server.base_url();
+        let request = AnswerRequestBuilder::default()
+            .messages(vec![ChatMessage::user("Is Rust fast?")])
+            .build()
+            .unwrap();
+
+        let deltas: Vec<AnswerDelta> = client
+            .answer_stream(&request).await
+            .unwrap()
+            .map(|delta| delta.unwrap())
+            .collect().await;
+
+        assert_eq!(
+            deltas,
+            vec![
+                AnswerDelta::Text("Fast.".to_string()),
+                AnswerDelta::Citation(Citation {
+                    number: None,
+                    url: "https://www.rust-lang.org/".to_string(),
+                    favicon: None,
+                    snippet: None,
+                    start_index: None,
+                    end_index: None,
+                }),
+                AnswerDelta::Text(" Done".to_string())
+            ]
+        );
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_search_context_headers() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/web/search")
+                .query_param("q", "coffee near me")
+                .header("X-Loc-Lat", "37.7749")
+                .header("X-Loc-Long", "-122.4194")
+                .header("X-Loc-Timezone", "America/Los_Angeles")
+                .header("X-Loc-City", "San Francisco")
+                .header("X-Loc-State", "CA")
+                .header("X-Loc-Country", "US")
+                .header("X-Loc-Postal-Code", "94103")
+                .header("User-Agent", "brave-rs-tests")
+                .header("Api-Version", "2023-01-01")
+                .header("Cache-Control", "no-cache");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    web_search_body(json!({
+                        "query": { "original": "coffee near me", "postal_code": "94103", "city": "San Francisco", "header_country": "us", "state": "CA" }
+                    }))
+                );
+        });
+        let mut client = BraveClient::new("test_key");
+        client.base_url = server.base_url();
+        client
+            .set_context(
+                SearchContextBuilder::default()
+                    .user_agent("brave-rs-tests")
+                    .api_version(Date::new(2023, 1, 1).unwrap())
+                    .build()
+                    .unwrap()
+            )
+            .unwrap();
+        let location = LocationHintsBuilder::default()
+            .latitude(37.7749)
+            .longitude(-122.4194)
+            .timezone("America/Los_Angeles")
+            .city("San Francisco")
+            .state("CA")
+            .country("US")
+            .postal_code("94103")
+            .build()
+            .unwrap();
+        let context = SearchContextBuilder::default().location(location).no_cache(true).build().unwrap();
+        let params = WebSearchQueryParamsBuilder::default().q("coffee near me").build().unwrap();
+
+        let response = client.with_context(&context).unwrap().web_search(&params).await.unwrap();
+
+        assert_eq!(response.query.city, "San Francisco");
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_web_search_rejects_invalid_query_before_sending() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/web/search");
+            then.status(200);
+        });
+        let mut client = BraveClient::new("test_key");
+        client.base_url = server.base_url();
+        let params = WebSearchQueryParamsBuilder::default().count(50u32).build().unwrap();
+
+        let result = client.web_search(&params).await;
+
+        let Err(BraveClientError::InvalidQuery(violations)) = result else {
+            panic!("Expected BraveClientError::InvalidQuery");
+        };
+        assert_eq!(violations, ["q must not be empty", "count is 50, maximum is 20"]);
+        assert!(client.web_search_by_query("   ").await.is_err());
+        mock.assert_hits(0);
+    }
+
+    #[tokio::test]
+    async fn test_builder_applies_headers_and_query_defaults() {
+        let server = MockServer::start();
+        let overridden = server.mock(|when, then| {
+            when.method(GET)
+                .path("/web/search")
+                .query_param("q", "bier")
+                .query_param("country", "AT")
+                .query_param("search_lang", "de")
+                .query_param("safesearch", "strict");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    web_search_body(json!({
+                        "query": { "original": "bier", "country": "at" }
+                    }))
+                );
+        });
+        let defaulted = server.mock(|when, then| {
+            when.method(GET)
+                .path("/web/search")
+                .query_param("q", "bier")
+                .query_param("country", "DE")
+                .query_param("search_lang", "de")
+                .query_param("safesearch", "strict")
+                .header("User-Agent", "staging-proxy-tests")
+                .header("X-Team", "search");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    web_search_body(json!({
+                        "query": { "original": "bier", "country": "de" }
+                    }))
+                );
+        });
+        let client = BraveClient::builder("test_key")
+            .base_url(format!("{}/", server.base_url()))
+            .user_agent("staging-proxy-tests")
+            .default_header("X-Team", "search")
+            .country(Country::De)
+            .search_lang(SearchLang::De)
+            .safesearch(SafeSearch::Strict)
+            .build()
+            .unwrap();
+        let params = WebSearchQueryParamsBuilder::default().q("bier").country(Country::At).build().unwrap();
+
+        let response = client.web_search_by_query("bier").await.unwrap();
+        client.web_search(&params).await.unwrap();
+
+        assert_eq!(response.query.country(), Some(Country::De));
+        defaulted.assert();
+        overridden.assert();
+    }
+
+    #[tokio::test]
+    async fn test_builder_request_timeout() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/web/search");
+            then.status(200).delay(Duration::from_millis(500));
+        });
+        let client = BraveClient::builder("test_key")
+            .base_url(server.base_url())
+            .timeout(Duration::from_millis(50))
+            .build()
+            .unwrap();
+
+        let result = client.web_search_by_query("slow").await;
+
+        assert!(matches!(result, Err(BraveClientError::ClientError(_))));
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_configuration() {
+        let invalid = [
+            BraveClient::builder("test_key").proxy("not a proxy url").build(),
+            BraveClient::builder("test_key").default_header("Bad Header", "value").build(),
+            BraveClient::builder("test_key")
+                .client(reqwest::Client::new())
+                .proxy("socks5://127.0.0.1:9050")
+                .build(),
+        ];
+
+        for result in invalid {
+            assert!(matches!(result, Err(BraveClientError::InvalidParameter(_))));
+        }
+        assert!(BraveClient::builder("test_key").proxy("socks5://127.0.0.1:9050").build().is_ok());
+    }
+
+    fn retry_test_client(server: &MockServer, initial_backoff: Duration) -> BraveClient {
+        let retry_policy = RetryPolicyBuilder::default()
+            .max_attempts(3u32)
+            .initial_backoff(initial_backoff)
+            .build()
+            .unwrap();
+        BraveClient::builder("test_key")
+            .base_url(server.base_url())
+            .retry_policy(retry_policy)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_retries_retryable_status_until_success() {
+        fn first_two_requests(_: &HttpMockRequest) -> bool {
+            static REQUESTS: AtomicUsize = AtomicUsize::new(0);
+            REQUESTS.fetch_add(1, Ordering::SeqCst) < 2
+        }
+        let server = MockServer::start();
+        let unavailable = server.mock(|when, then| {
+            when.method(GET).path("/suggest/search").matches(first_two_requests);
+            then.status(503);
+        });
+        let ok = server.mock(|when, then| {
+            when.method(GET).path("/suggest/search").query_param("q", "rus");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    serde_json::json!({
+                        "type": "suggest",
+                        "query": { "original": "rus" },
+                        "results": [{ "query": "rust" }]
+                    })
+                );
+        });
+        let client = retry_test_client(&server, Duration::from_millis(10));
+        let params = SuggestQueryParamsBuilder::default().q("rus").build().unwrap();
+
+        let response = client.suggest(&params).await.unwrap();
+
+        assert_eq!(response.results[0].query, "rust");
+        unavailable.assert_hits(2);
+        ok.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_honors_retry_after() {
+        fn first_request(_: &HttpMockRequest) -> bool {
+            static REQUESTS: AtomicUsize = AtomicUsize::new(0);
+            REQUESTS.fetch_add(1, Ordering::SeqCst) == 0
+        }
+        let server = MockServer::start();
+        let limited = server.mock(|when, then| {
+            when.method(GET).path("/suggest/search").matches(first_request);
+            then.status(429).header("Retry-After", "0");
+        });
+        let ok = server.mock(|when, then| {
+            when.method(GET).path("/suggest/search");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    serde_json::json!({
+                        "type": "suggest",
+                        "query": { "original": "rus" },
+                        "results": []
+                    })
+                );
+        });
+        // A backoff this long would time the test out unless Retry-After replaces it.
+        let client = retry_test_client(&server, Duration::from_secs(60));
+        let params = SuggestQueryParamsBuilder::default().q("rus").build().unwrap();
+
+        let response = tokio::time::timeout(Duration::from_secs(5), client.suggest(&params)).await;
+
+        assert!(response.unwrap().is_ok());
+        limited.assert_hits(1);
+        ok.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up() {
+        let server = MockServer::start();
+        let unavailable = server.mock(|when, then| {
+            when.method(GET).path("/suggest/search").query_param("q", "down");
+            then.status(503);
+        });
+        let bad_request = server.mock(|when, then| {
+            when.method(GET).path("/suggest/search").query_param("q", "bad");
+            then.status(422);
+        });
+        let client = retry_test_client(&server, Duration::from_millis(10));
+        let down = SuggestQueryParamsBuilder::default().q("down").build().unwrap();
+        let bad = SuggestQueryParamsBuilder::default().q("bad").build().unwrap();
+
+        let exhausted = client.suggest(&down).await;
+        let not_retried = client.suggest(&bad).await;
+
+        assert!(matches!(exhausted, Err(BraveClientError::HttpError(msg)) if msg.contains("503")));
+        assert!(matches!(not_retried, Err(BraveClientError::HttpError(msg)) if msg.contains("422")));
+        unavailable.assert_hits(3);
+        bad_request.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_info_is_readable_after_each_call() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/spellcheck/search");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .header("X-RateLimit-Limit", "20, 20000000")
+                .header("X-RateLimit-Policy", "20;w=1, 20000000;w=2592000")
+                .header("X-RateLimit-Remaining", "19, 19999000")
+                .header("X-RateLimit-Reset", "1, 1419704")
+                .json_body(
+                    serde_json::json!({
+                        "type": "spellcheck",
+                        "query": { "original": "helo" },
+                        "results": [{ "query": "hello" }]
+                    })
+                );
+        });
+        let client = BraveClient::builder("test_key")
+            .base_url(server.base_url())
+            .rate_limiter(RateLimiter::new(20, Some(20_000_000)).unwrap())
+            .build()
+            .unwrap();
+        let params = SpellcheckQueryParamsBuilder::default().q("helo").build().unwrap();
+
+        assert_eq!(client.rate_limit_info(), None);
+        client.spellcheck(&params).await.unwrap();
+
+        let info = client.rate_limit_info().unwrap();
+        assert_eq!(info.per_second().map(|window| window.remaining), Some(19));
+        assert_eq!(info.per_month().map(|window| window.limit), Some(20_000_000));
+        assert_eq!(
+            info.per_month().and_then(|window| window.window),
+            Some(Duration::from_secs(2_592_000))
+        );
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_key_pool_fails_over_from_rejected_and_rate_limited_keys() {
+        let server = MockServer::start();
+        let revoked = server.mock(|when, then| {
+            when.method(GET).path("/spellcheck/search").header("X-Subscription-Token", "revoked");
+            then.status(401);
+        });
+        let limited = server.mock(|when, then| {
+            when.method(GET).path("/spellcheck/search").header("X-Subscription-Token", "limited");
+            then.status(429).header("Retry-After", "30");
+        });
+        let working = server.mock(|when, then| {
+            when.method(GET).path("/spellcheck/search").header("X-Subscription-Token", "working");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    serde_json::json!({
+                        "type": "spellcheck",
+                        "query": { "original": "helo" },
+                        "results": []
+                    })
+                );
+        });
+        let pool = ApiKeyPool::new(["revoked", "limited", "working"], RotationStrategy::RoundRobin);
+        let client = BraveClient::builder("unused")
+            .base_url(server.base_url())
+            .key_pool(pool.unwrap())
+            .build()
+            .unwrap();
+        let params = SpellcheckQueryParamsBuilder::default().q("helo").build().unwrap();
+
+        client.spellcheck(&params).await.unwrap();
+        client.spellcheck(&params).await.unwrap();
+
+        revoked.assert_hits(1);
+        limited.assert_hits(1);
+        working.assert_hits(2);
+        let stats = client.key_stats();
+        assert!(!stats[0].healthy);
+        assert_eq!(stats[1].rate_limited, 1);
+        assert!(stats[1].cooldown.is_some_and(|cooldown| cooldown > Duration::from_secs(20)));
+        assert_eq!((stats[2].requests, stats[2].successes), (2, 2));
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_pool_key_does_not_throttle_other_keys() {
+        let server = MockServer::start();
+        let mock = |key: &'static str, remaining: &'static str| {
+            server.mock(move |when, then| {
+                when.method(GET).path("/web/search").header("X-Subscription-Token", key);
+                then.status(200)
+                    .header("Content-Type", "application/json")
+                    .header("X-RateLimit-Limit", "20, 100")
+                    .header("X-RateLimit-Policy", "20;w=1, 100;w=2592000")
+                    .header("X-RateLimit-Remaining", remaining)
+                    .header("X-RateLimit-Reset", "1, 1419704")
+                    .json_body(web_search_body(json!({})));
+            })
+        };
+        let exhausted = mock("exhausted", "19, 0");
+        let healthy = mock("healthy", "19, 90");
+        let pool = ApiKeyPool::new(["exhausted", "healthy"], RotationStrategy::RemainingQuota);
+        let client = BraveClient::builder("unused")
+            .base_url(server.base_url())
+            .key_pool(pool.unwrap())
+            .rate_limiter(RateLimiter::new(20, Some(100)).unwrap().with_max_wait(Duration::ZERO))
+            .build()
+            .unwrap();
+
+        for _ in 0..3 {
+            client.web_search_by_query("rust").await.unwrap();
+        }
+
+        exhausted.assert_hits(1);
+        healthy.assert_hits(2);
+    }
+
+    #[tokio::test]
+    async fn test_cache_serves_repeated_queries() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/web/search").query_param("q", "rust");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(web_search_body(json!({})));
+        });
+        let client = BraveClient::builder("test_key")
+            .base_url(server.base_url())
+            .cache(MemoryCache::new(16, Duration::from_secs(60)))
+            .build()
+            .unwrap();
+        let berlin = SearchContextBuilder::default()
+            .location(LocationHintsBuilder::default().city("Berlin").build().unwrap())
+            .build()
+            .unwrap();
+
+        let first = client.web_search_by_query("rust").await.unwrap();
+        let second = client.web_search_by_query("rust").await.unwrap();
+        let bypassed = client.with_cache_mode(CacheMode::Bypass).web_search_by_query("rust").await;
+        let refreshed = client.with_cache_mode(CacheMode::Refresh).web_search_by_query("rust").await;
+        let located = client.with_context(&berlin).unwrap().web_search_by_query("rust").await;
+
+        assert_eq!(first.cache, CacheStatus::Miss);
+        assert!(second.cache.is_hit());
+        assert_eq!(bypassed.unwrap().cache, CacheStatus::NotCached);
+        assert_eq!(refreshed.unwrap().cache, CacheStatus::Refreshed);
+        assert_eq!(located.unwrap().cache, CacheStatus::Miss);
+        assert_eq!(client.stats().cache_hits, 1);
+        mock.assert_hits(4);
+    }
+
+    #[tokio::test]
+    async fn test_no_cache_context_refreshes_cached_entry() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/web/search").query_param("q", "rust");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(web_search_body(json!({})));
+        });
+        let client = BraveClient::builder("test_key")
+            .base_url(server.base_url())
+            .cache(MemoryCache::new(16, Duration::from_secs(60)))
+            .build()
+            .unwrap();
+        let no_cache = SearchContextBuilder::default().no_cache(true).build().unwrap();
+
+        client.web_search_by_query("rust").await.unwrap();
+        let refreshed = client.with_context(&no_cache).unwrap().web_search_by_query("rust").await;
+        let cached = client.web_search_by_query("rust").await.unwrap();
+
+        assert_eq!(refreshed.unwrap().cache, CacheStatus::Refreshed);
+        assert!(cached.cache.is_hit());
+        mock.assert_hits(2);
+    }
+
+    #[tokio::test]
+    async fn test_web_search_pages_until_no_more_results() {
+        let server = MockServer::start();
+        let page = |offset: u32, more_results_available: bool| {
+            let title = format!("Result {}", offset);
+            server.mock(move |when, then| {
+                let when = when.method(GET).path("/web/search").query_param("q", "rust");
+                // The first page is sent without an offset; it is registered last so the mocks
+                // for later pages match first.
+                if offset > 0 {
+                    when.query_param("offset", offset.to_string());
+                }
+                then.status(200)
+                    .header("Content-Type", "application/json")
+                    .json_body(
+                        web_search_body(json!({
+                            "query": { "more_results_available": more_results_available },
+                            "web": {
+                                "type": "search",
+                                "results": [{ "type": "search_result", "url": "https://example.com", "title": title, "description": "" }]
+                            }
+                        }))
+                    );
+            })
+        };
+        let second_page = page(1, true);
+        let last_page = page(2, false);
+        let first_page = page(0, true);
+        let client = BraveClient::builder("test_key").base_url(server.base_url()).build().unwrap();
+        let params = WebSearchQueryParamsBuilder::default().q("rust").build().unwrap();
+
+        let pages: Vec<_> = client.web_search_pages(&params, PageOptions::default()).collect().await;
+        assert_eq!(pages.len(), 3);
+        assert!(!pages[2].as_ref().unwrap().query.more_results_available);
+
+        let options = PageOptions { max_pages: Some(2), delay: Some(Duration::from_millis(50)) };
+        let started = std::time::Instant::now();
+        let titles: Vec<String> = client
+            .web_search_results(&params, options)
+            .map(|result| result.unwrap().title)
+            .collect().await;
+        assert_eq!(titles, ["Result 0", "Result 1"]);
+        assert!(started.elapsed() >= Duration::from_millis(50));
+
+        let last = WebSearchQueryParams { offset: Some(WebSearchQueryParams::MAX_OFFSET), ..params };
+        let pages: Vec<_> = client.web_search_pages(&last, PageOptions::default()).collect().await;
+        assert_eq!(pages.len(), 1);
+        assert!(pages[0].as_ref().unwrap().query.more_results_available);
+        first_page.assert_hits(3);
+        second_page.assert_hits(2);
+        last_page.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_identical_searches_are_coalesced() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/web/search").query_param("q", "rust");
+            then.status(200)
+                .delay(Duration::from_millis(200))
+                .header("Content-Type", "application/json")
+                .json_body(web_search_body(json!({})));
+        });
+        let client = BraveClient::builder("test_key")
+            .base_url(server.base_url())
+            .coalesce_requests(true)
+            .build()
+            .unwrap();
+
+        let searches: Vec<_> = (0..4)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move { client.web_search_by_query("rust").await })
+            })
+            .collect();
+        for search in searches {
+            assert_eq!(search.await.unwrap().unwrap().query.original, "rust");
+        }
+
+        mock.assert_hits(1);
+        assert_eq!(client.stats(), ClientStats { requests: 1, cache_hits: 0, coalesced: 3 });
+    }
+
+    #[tokio::test]
+    async fn test_web_search_sections_distinguish_filtered_from_empty() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/web/search")
+                .query_param("q", "rust")
+                .query_param("result_filter", "news,web");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(
+                    web_search_body(json!({
+                        "web": { "type": "search", "results": [] }
+                    }))
+                );
+        });
+        let mut client = BraveClient::new("test_key");
+        client.base_url = server.base_url();
+        let params = WebSearchQueryParamsBuilder::default()
+            .q("rust")
+            .result_filter([ResultSection::Web, ResultSection::News])
+            .build()
+            .unwrap();
+
+        let response = client.web_search(&params).await.unwrap();
+
+        assert!(matches!(response.web_section(), Section::Results(web) if web.results.is_empty()));
+        assert!(matches!(response.news_section(), Section::Empty));
+        assert!(matches!(response.faq_section(), Section::NotRequested));
+        assert!(!response.videos_section().is_requested());
+        mock.assert();
+    }
 }
@@ -0,0 +1,362 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    fs,
+    path::PathBuf,
+    sync::Mutex,
+    time::{ Duration, SystemTime, UNIX_EPOCH },
+};
+
+use serde::{ Deserialize, Serialize };
+
+use crate::{
+    brave::BraveClientError,
+    types::{
+        ImageSearchApiResponse,
+        LocalDescriptionsSearchApiResponse,
+        LocalPoiSearchApiResponse,
+        NewsSearchApiResponse,
+        RichSearchApiResponse,
+        SpellcheckApiResponse,
+        SuggestApiResponse,
+        VideoSearchApiResponse,
+        WebSearchApiResponse,
+    },
+};
+
+/// Whether a response was served from the client's [`SearchCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CacheStatus {
+    /// No cache is configured, the endpoint is not cached or the request bypassed the cache.
+    #[default]
+    NotCached,
+    /// Fetched from the API and stored.
+    Miss,
+    /// Served from the cache, stored `age` ago.
+    Hit {
+        age: Duration,
+    },
+    /// Fetched from the API despite a cached entry, which it replaced.
+    Refreshed,
+}
+
+impl CacheStatus {
+    pub fn is_hit(&self) -> bool {
+        matches!(self, CacheStatus::Hit { .. })
+    }
+
+    /// Combines the statuses of the pages merged into one response: a hit only when every page
+    /// was, otherwise the first page that was fetched.
+    pub(crate) fn merge(self, other: CacheStatus) -> CacheStatus {
+        match (self, other) {
+            (CacheStatus::Hit { age }, CacheStatus::Hit { age: other_age }) => {
+                CacheStatus::Hit { age: age.max(other_age) }
+            }
+            (CacheStatus::Hit { .. }, other) => other,
+            (status, _) => status,
+        }
+    }
+}
+
+/// How a request uses the client's cache. See [`BraveClient::with_cache_mode`].
+///
+/// [`BraveClient::with_cache_mode`]: crate::BraveClient::with_cache_mode
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Serve fresh entries from the cache and store new responses.
+    #[default]
+    Use,
+    /// Neither read nor write the cache.
+    Bypass,
+    /// Always fetch from the API and store the new response.
+    Refresh,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResponse {
+    /// The raw JSON body returned by the API.
+    pub body: String,
+    pub stored_at: SystemTime,
+}
+
+/// Storage for raw API responses, keyed on the request's normalized params and location
+/// headers. Backends are responsible for expiring entries. Caching is best-effort: backends
+/// swallow their own storage errors.
+pub trait SearchCache: Debug + Send + Sync {
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+    fn put(&self, key: &str, body: &str);
+    fn remove(&self, key: &str);
+}
+
+/// Responses carrying a [`CacheStatus`].
+pub(crate) trait CacheMetadata {
+    fn set_cache_status(&mut self, status: CacheStatus);
+}
+
+macro_rules! impl_cache_metadata {
+    ($($response:ty),+) => {
+        $(
+            impl CacheMetadata for $response {
+                fn set_cache_status(&mut self, status: CacheStatus) {
+                    self.cache = status;
+                }
+            }
+        )+
+    };
+}
+
+impl_cache_metadata!(
+    WebSearchApiResponse,
+    ImageSearchApiResponse,
+    NewsSearchApiResponse,
+    VideoSearchApiResponse,
+    SuggestApiResponse,
+    SpellcheckApiResponse,
+    LocalPoiSearchApiResponse,
+    LocalDescriptionsSearchApiResponse,
+    RichSearchApiResponse
+);
+
+/// Builds a cache key from the base URL, the endpoint path, its query params sorted by name
+/// (keeping the order of repeated params) and the request headers that change results. The base
+/// URL keeps clients sharing a cache but talking to different servers apart.
+pub(crate) fn cache_key(
+    base_url: &str,
+    url_path: &str,
+    headers: &[(&'static str, String)]
+) -> String {
+    let (path, query) = url_path.split_once('?').unwrap_or((url_path, ""));
+    let mut params: Vec<&str> = query.split('&').filter(|param| !param.is_empty()).collect();
+    params.sort_by_key(|param| param.split_once('=').map_or(*param, |(name, _)| name));
+    let mut headers: Vec<String> = headers
+        .iter()
+        .filter(|(name, _)| *name != "Cache-Control")
+        .map(|(name, value)| format!("{}={}", name.to_ascii_lowercase(), value))
+        .collect();
+    headers.sort();
+    format!("{}{}?{}|{}", base_url, path, params.join("&"), headers.join("&"))
+}
+
+fn is_fresh(stored_at: SystemTime, ttl: Duration) -> bool {
+    stored_at.elapsed().is_ok_and(|age| age <= ttl)
+}
+
+/// In-memory cache evicting the least recently used entry once `capacity` is reached.
+#[derive(Debug)]
+pub struct MemoryCache {
+    capacity: usize,
+    ttl: Duration,
+    state: Mutex<MemoryState>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryState {
+    entries: HashMap<String, MemoryEntry>,
+    clock: u64,
+}
+
+#[derive(Debug)]
+struct MemoryEntry {
+    response: CachedResponse,
+    last_used: u64,
+}
+
+impl MemoryCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        MemoryCache { capacity, ttl, state: Mutex::default() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MemoryState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl SearchCache for MemoryCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let mut state = self.lock();
+        state.clock += 1;
+        let clock = state.clock;
+        let entry = state.entries.get_mut(key)?;
+        if !is_fresh(entry.response.stored_at, self.ttl) {
+            state.entries.remove(key);
+            return None;
+        }
+        entry.last_used = clock;
+        Some(entry.response.clone())
+    }
+
+    fn put(&self, key: &str, body: &str) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut state = self.lock();
+        state.clock += 1;
+        if !state.entries.contains_key(key) && state.entries.len() >= self.capacity {
+            let ttl = self.ttl;
+            state.entries.retain(|_, entry| is_fresh(entry.response.stored_at, ttl));
+            if state.entries.len() >= self.capacity {
+                let least_recent = state.entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used)
+                    .map(|(key, _)| key.clone());
+                if let Some(least_recent) = least_recent {
+                    state.entries.remove(&least_recent);
+                }
+            }
+        }
+        let entry = MemoryEntry {
+            response: CachedResponse { body: body.to_string(), stored_at: SystemTime::now() },
+            last_used: state.clock,
+        };
+        state.entries.insert(key.to_string(), entry);
+    }
+
+    fn remove(&self, key: &str) {
+        self.lock().entries.remove(key);
+    }
+}
+
+/// Cache storing one JSON file per entry in a directory, so entries survive restarts and can be
+/// shared between processes such as CI jobs.
+#[derive(Debug)]
+pub struct DiskCache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DiskEntry {
+    key: String,
+    stored_at: u64,
+    body: String,
+}
+
+impl DiskCache {
+    /// Stores entries in `dir`, which is created when missing.
+    pub fn new(dir: impl Into<PathBuf>, ttl: Duration) -> Result<Self, BraveClientError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).map_err(|err| {
+            BraveClientError::ClientError(format!("cache dir {}: {}", dir.display(), err))
+        })?;
+        Ok(DiskCache { dir, ttl })
+    }
+
+    /// Entries are named after a FNV-1a hash of the key, which is stable across builds.
+    fn path(&self, key: &str) -> PathBuf {
+        let hash = key
+            .bytes()
+            .fold(0xcbf29ce484222325u64, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+            });
+        self.dir.join(format!("{:016x}.json", hash))
+    }
+}
+
+impl SearchCache for DiskCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let path = self.path(key);
+        let entry: DiskEntry = serde_json::from_slice(&fs::read(&path).ok()?).ok()?;
+        if entry.key != key {
+            return None;
+        }
+        let stored_at = UNIX_EPOCH + Duration::from_secs(entry.stored_at);
+        if !is_fresh(stored_at, self.ttl) {
+            let _ = fs::remove_file(path);
+            return None;
+        }
+        Some(CachedResponse { body: entry.body, stored_at })
+    }
+
+    fn put(&self, key: &str, body: &str) {
+        let stored_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or_default();
+        let entry = DiskEntry { key: key.to_string(), stored_at, body: body.to_string() };
+        if let Ok(json) = serde_json::to_vec(&entry) {
+            // Write to a temporary file first so readers never see a partial entry.
+            let path = self.path(key);
+            let tmp = path.with_extension("json.tmp");
+            if fs::write(&tmp, json).is_ok() {
+                let _ = fs::rename(tmp, path);
+            }
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let _ = fs::remove_file(self.path(key));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_is_normalized() {
+        let headers = [
+            ("X-Loc-City", String::from("Berlin")),
+            ("Cache-Control", String::from("no-cache")),
+        ];
+
+        let production = "https://api.search.brave.com/res/v1";
+        let staging = "https://staging.example.com/res/v1";
+
+        assert_eq!(
+            cache_key(production, "/web/search?q=rust&count=5&goggles=b&goggles=a", &headers),
+            cache_key(production, "/web/search?count=5&goggles=b&q=rust&goggles=a", &headers[..1])
+        );
+        assert_ne!(
+            cache_key(production, "/web/search?q=rust", &headers),
+            cache_key(production, "/web/search?q=rust", &[])
+        );
+        assert_ne!(
+            cache_key(production, "/web/search?q=rust", &[]),
+            cache_key(staging, "/web/search?q=rust", &[])
+        );
+    }
+
+    #[test]
+    fn test_memory_cache_evicts_least_recently_used_and_expired() {
+        let cache = MemoryCache::new(2, Duration::from_secs(60));
+        cache.put("a", "1");
+        cache.put("b", "2");
+        cache.get("a");
+        cache.put("c", "3");
+
+        assert!(cache.get("b").is_none());
+        assert_eq!(cache.get("a").map(|response| response.body), Some(String::from("1")));
+        assert_eq!(cache.len(), 2);
+
+        let expired = MemoryCache::new(2, Duration::ZERO);
+        expired.put("a", "1");
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(expired.get("a").is_none());
+    }
+
+    #[test]
+    fn test_disk_cache_round_trips_entries() {
+        let dir = std::env::temp_dir().join(format!("brave-rs-cache-{}", std::process::id()));
+        let cache = DiskCache::new(&dir, Duration::from_secs(60)).unwrap();
+
+        cache.put("/web/search?q=rust|", r#"{"type":"search"}"#);
+
+        let reopened = DiskCache::new(&dir, Duration::from_secs(60)).unwrap();
+        assert_eq!(
+            reopened.get("/web/search?q=rust|").map(|response| response.body),
+            Some(String::from(r#"{"type":"search"}"#))
+        );
+        assert!(reopened.get("/web/search?q=go|").is_none());
+        reopened.remove("/web/search?q=rust|");
+        assert!(cache.get("/web/search?q=rust|").is_none());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
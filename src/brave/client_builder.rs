@@ -0,0 +1,225 @@
+use std::{ sync::Arc, time::Duration };
+
+use reqwest::header::{ HeaderMap, HeaderName, HeaderValue };
+
+use crate::{
+    brave::{
+        ApiKeyPool,
+        BraveClient,
+        BraveClientError,
+        CacheMode,
+        RateLimiter,
+        RetryPolicy,
+        SearchCache,
+    },
+    types::{ Country, QueryDefaults, SafeSearch, SearchContext, SearchLang },
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://api.search.brave.com/res/v1";
+
+/// Configures a [`BraveClient`]. Created with [`BraveClient::builder`].
+#[derive(Debug, Clone)]
+pub struct BraveClientBuilder {
+    keys: Arc<ApiKeyPool>,
+    base_url: String,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<String>,
+    client: Option<reqwest::Client>,
+    user_agent: Option<String>,
+    default_headers: Vec<(String, String)>,
+    context: SearchContext,
+    defaults: QueryDefaults,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    cache: Option<Arc<dyn SearchCache>>,
+    coalesce_requests: bool,
+}
+
+impl BraveClientBuilder {
+    pub fn new(api_key: &str) -> Self {
+        BraveClientBuilder {
+            keys: Arc::new(ApiKeyPool::single(api_key)),
+            base_url: String::from(DEFAULT_BASE_URL),
+            timeout: None,
+            connect_timeout: None,
+            proxy: None,
+            client: None,
+            user_agent: None,
+            default_headers: vec![],
+            context: SearchContext::default(),
+            defaults: QueryDefaults::default(),
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: None,
+            cache: None,
+            coalesce_requests: false,
+        }
+    }
+
+    /// Spreads requests over several subscription tokens, replacing the builder's key.
+    pub fn key_pool(mut self, key_pool: impl Into<Arc<ApiKeyPool>>) -> Self {
+        self.keys = key_pool.into();
+        self
+    }
+
+    /// Sends requests to `base_url` instead of the public API, e.g. a staging proxy or mock
+    /// server. A trailing `/` is ignored.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Limits each request, from sending it to reading the full response body.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Routes all requests through an `http://`, `https://` or `socks5://` proxy.
+    pub fn proxy(mut self, proxy_url: impl Into<String>) -> Self {
+        self.proxy = Some(proxy_url.into());
+        self
+    }
+
+    /// Uses a preconfigured `reqwest::Client`. Connection settings (`connect_timeout`, `proxy`)
+    /// must then be configured on that client instead.
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Adds a header sent with every request. Headers set by the client or the search context
+    /// take precedence.
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.default_headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the location and client headers sent with every request.
+    pub fn context(mut self, context: SearchContext) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Country applied to every query that does not set one.
+    pub fn country(mut self, country: Country) -> Self {
+        self.defaults.country = Some(country);
+        self
+    }
+
+    /// Search language applied to every query that does not set one.
+    pub fn search_lang(mut self, search_lang: SearchLang) -> Self {
+        self.defaults.search_lang = Some(search_lang);
+        self
+    }
+
+    /// Safesearch level applied to every query that does not set one.
+    pub fn safesearch(mut self, safesearch: SafeSearch) -> Self {
+        self.defaults.safesearch = Some(safesearch);
+        self
+    }
+
+    /// Retries failed requests according to `retry_policy`. Requests are sent once by default.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Throttles requests client-side. Pass the same `Arc` to several clients to share a plan's
+    /// limits between them.
+    pub fn rate_limiter(mut self, rate_limiter: impl Into<Arc<RateLimiter>>) -> Self {
+        self.rate_limiter = Some(rate_limiter.into());
+        self
+    }
+
+    /// Caches search responses. Summaries and answers are never cached.
+    pub fn cache(mut self, cache: impl SearchCache + 'static) -> Self {
+        self.cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Caches search responses in a cache shared with other clients.
+    pub fn shared_cache(mut self, cache: Arc<dyn SearchCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Lets concurrent searches with identical params and headers share one HTTP request. Every
+    /// caller receives the response, or a clone of the error.
+    pub fn coalesce_requests(mut self, coalesce_requests: bool) -> Self {
+        self.coalesce_requests = coalesce_requests;
+        self
+    }
+
+    pub fn build(self) -> Result<BraveClient, BraveClientError> {
+        let mut context = self.context;
+        if let Some(user_agent) = self.user_agent {
+            context.user_agent = Some(user_agent);
+        }
+        context.validate()?;
+
+        let mut default_headers = HeaderMap::new();
+        for (name, value) in &self.default_headers {
+            let header_name = HeaderName::from_bytes(name.as_bytes()).map_err(|err| {
+                BraveClientError::InvalidParameter(format!("header name `{}`: {}", name, err))
+            })?;
+            let header_value = HeaderValue::from_str(value).map_err(|err| {
+                BraveClientError::InvalidParameter(format!("header `{}` value: {}", name, err))
+            })?;
+            default_headers.append(header_name, header_value);
+        }
+
+        let client = match self.client {
+            Some(client) => {
+                if self.proxy.is_some() || self.connect_timeout.is_some() {
+                    return Err(
+                        BraveClientError::InvalidParameter(
+                            "proxy and connect_timeout cannot be combined with a custom client".into()
+                        )
+                    );
+                }
+                client
+            }
+            None => {
+                let mut client_builder = reqwest::Client::builder();
+                if let Some(connect_timeout) = self.connect_timeout {
+                    client_builder = client_builder.connect_timeout(connect_timeout);
+                }
+                if let Some(proxy_url) = &self.proxy {
+                    let proxy = reqwest::Proxy::all(proxy_url).map_err(|err| {
+                        BraveClientError::InvalidParameter(format!("proxy `{}`: {}", proxy_url, err))
+                    })?;
+                    client_builder = client_builder.proxy(proxy);
+                }
+                client_builder.build()?
+            }
+        };
+
+        Ok(BraveClient {
+            keys: self.keys,
+            base_url: self.base_url,
+            client,
+            context,
+            default_headers,
+            timeout: self.timeout,
+            defaults: self.defaults,
+            retry_policy: self.retry_policy,
+            rate_limiter: self.rate_limiter,
+            rate_limit: Arc::default(),
+            cache: self.cache,
+            cache_mode: CacheMode::default(),
+            coalescer: self.coalesce_requests.then(Arc::default),
+            stats: Arc::default(),
+        })
+    }
+}
@@ -0,0 +1,80 @@
+use std::{ collections::HashMap, future::Future, sync::{ Arc, Mutex } };
+
+use tokio::sync::OnceCell;
+
+use crate::brave::BraveClientError;
+
+type Flight = Arc<OnceCell<Result<String, BraveClientError>>>;
+
+/// Lets concurrent identical requests share one in-flight HTTP request ("singleflight").
+#[derive(Debug, Default)]
+pub(crate) struct Coalescer {
+    flights: Mutex<HashMap<String, Flight>>,
+}
+
+impl Coalescer {
+    /// Runs `fetch` unless a request with the same `key` is already in flight, in which case
+    /// its result is shared. Returns the result and whether it was shared. If the caller
+    /// running the request is cancelled, one of the waiting callers takes over.
+    pub(crate) async fn run<F>(
+        &self,
+        key: String,
+        fetch: F
+    ) -> (Result<String, BraveClientError>, bool)
+        where F: Future<Output = Result<String, BraveClientError>>
+    {
+        let flight = self.lock().entry(key.clone()).or_default().clone();
+        let mut fetched = false;
+        let result = flight
+            .get_or_init(|| async {
+                fetched = true;
+                fetch.await
+            }).await
+            .clone();
+        let mut flights = self.lock();
+        if flights.get(&key).is_some_and(|current| Arc::ptr_eq(current, &flight)) {
+            flights.remove(&key);
+        }
+        (result, !fetched)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Flight>> {
+        self.flights.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_coalescer_shares_in_flight_results() {
+        let coalescer = Arc::new(Coalescer::default());
+        let fetches = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..5)
+            .map(|_| {
+                let coalescer = coalescer.clone();
+                let fetches = fetches.clone();
+                tokio::spawn(async move {
+                    coalescer.run(String::from("key"), async move {
+                        fetches.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        Err(BraveClientError::HttpError(String::from("HTTP error 503")))
+                    }).await
+                })
+            })
+            .collect();
+        let mut shared = 0;
+        for task in tasks {
+            let (result, was_shared) = task.await.unwrap();
+            assert!(matches!(result, Err(BraveClientError::HttpError(_))));
+            shared += was_shared as usize;
+        }
+
+        assert_eq!(fetches.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(shared, 4);
+        assert!(coalescer.lock().is_empty());
+    }
+}
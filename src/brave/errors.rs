@@ -1,16 +1,18 @@
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum BraveClientError {
     #[error("Client error: {0}")] ClientError(String),
     #[error("Http error: {0}")] HttpError(String),
     #[error("Unable to deserialize response")] ResponseDeserializationError(String),
+    #[error("Summary unavailable: {0}")] SummaryUnavailable(String),
+    #[error("Invalid parameter {0}")] InvalidParameter(String),
+    #[error("Invalid query: {}", .0.join("; "))] InvalidQuery(Vec<String>),
+    #[error("Rate limited, capacity available in {0:?}")] RateLimited(std::time::Duration),
 }
 
 impl From<reqwest::Error> for BraveClientError {
     fn from(err: reqwest::Error) -> Self {
-        if err.is_status() {
-            if let Some(status) = err.status() {
-                return BraveClientError::HttpError(format!("HTTP error {}: {}", status, err));
-            }
+        if err.is_status() && let Some(status) = err.status() {
+            return BraveClientError::HttpError(format!("HTTP error {}: {}", status, err));
         }
         BraveClientError::ClientError(err.to_string())
     }
@@ -0,0 +1,300 @@
+use std::{ sync::Mutex, time::{ Duration, Instant } };
+
+use reqwest::StatusCode;
+
+use crate::{ brave::BraveClientError, types::RateLimitInfo };
+
+/// How an [`ApiKeyPool`] picks the key for the next request among the available keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RotationStrategy {
+    #[default]
+    RoundRobin,
+    /// The key that has sent the fewest requests.
+    LeastUsed,
+    /// The key with the most remaining monthly quota according to its last rate limit headers.
+    /// Keys that have not reported a quota yet are tried first.
+    RemainingQuota,
+}
+
+/// Usage of one key in an [`ApiKeyPool`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyStats {
+    pub index: usize,
+    /// The last four characters of the key, for logs and dashboards.
+    pub key_suffix: String,
+    pub requests: u64,
+    pub successes: u64,
+    pub failures: u64,
+    pub rate_limited: u64,
+    /// `false` once the API rejected the key with 401 or 403.
+    pub healthy: bool,
+    /// Time left until the key is used again after a 429.
+    pub cooldown: Option<Duration>,
+    pub rate_limit: Option<RateLimitInfo>,
+}
+
+/// Subscription tokens shared by a client. Keys are marked unhealthy when the API rejects them
+/// and cooled down when they are rate limited; requests then fail over to the other keys.
+#[derive(Debug)]
+pub struct ApiKeyPool {
+    strategy: RotationStrategy,
+    cooldown: Duration,
+    state: Mutex<PoolState>,
+}
+
+#[derive(Debug)]
+struct PoolState {
+    keys: Vec<KeyState>,
+    next: usize,
+}
+
+#[derive(Debug)]
+struct KeyState {
+    key: String,
+    requests: u64,
+    successes: u64,
+    failures: u64,
+    rate_limited: u64,
+    healthy: bool,
+    cooldown_until: Option<Instant>,
+    rate_limit: Option<RateLimitInfo>,
+}
+
+impl KeyState {
+    fn new(key: String) -> Self {
+        KeyState {
+            key,
+            requests: 0,
+            successes: 0,
+            failures: 0,
+            rate_limited: 0,
+            healthy: true,
+            cooldown_until: None,
+            rate_limit: None,
+        }
+    }
+
+    fn suffix(&self) -> String {
+        let chars: Vec<char> = self.key.chars().collect();
+        chars[chars.len().saturating_sub(4)..].iter().collect()
+    }
+
+    fn is_available(&self, now: Instant) -> bool {
+        self.healthy && self.cooldown_until.is_none_or(|until| until <= now)
+    }
+
+    fn remaining_quota(&self) -> u64 {
+        self.rate_limit
+            .as_ref()
+            .and_then(RateLimitInfo::per_month)
+            .map_or(u64::MAX, |window| window.remaining)
+    }
+}
+
+impl ApiKeyPool {
+    pub fn new<I, K>(keys: I, strategy: RotationStrategy) -> Result<Self, BraveClientError>
+        where I: IntoIterator<Item = K>, K: Into<String>
+    {
+        let keys: Vec<KeyState> = keys
+            .into_iter()
+            .map(|key| KeyState::new(key.into()))
+            .collect();
+        if keys.is_empty() {
+            return Err(
+                BraveClientError::InvalidParameter("key pool requires at least one key".into())
+            );
+        }
+        Ok(ApiKeyPool::from_keys(keys, strategy))
+    }
+
+    pub(crate) fn single(key: &str) -> Self {
+        ApiKeyPool::from_keys(vec![KeyState::new(key.to_string())], RotationStrategy::RoundRobin)
+    }
+
+    fn from_keys(keys: Vec<KeyState>, strategy: RotationStrategy) -> Self {
+        ApiKeyPool {
+            strategy,
+            cooldown: Duration::from_secs(60),
+            state: Mutex::new(PoolState { keys, next: 0 }),
+        }
+    }
+
+    /// How long a rate limited key is skipped when the 429 carries no reset hint. Defaults to
+    /// one minute.
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn stats(&self) -> Vec<KeyStats> {
+        let now = Instant::now();
+        self.lock()
+            .keys
+            .iter()
+            .enumerate()
+            .map(|(index, key)| KeyStats {
+                index,
+                key_suffix: key.suffix(),
+                requests: key.requests,
+                successes: key.successes,
+                failures: key.failures,
+                rate_limited: key.rate_limited,
+                healthy: key.healthy,
+                cooldown: key.cooldown_until
+                    .map(|until| until.saturating_duration_since(now))
+                    .filter(|cooldown| !cooldown.is_zero()),
+                rate_limit: key.rate_limit.clone(),
+            })
+            .collect()
+    }
+
+    /// Marks every key healthy again and clears cooldowns, e.g. after rotating credentials.
+    pub fn reset_health(&self) {
+        for key in &mut self.lock().keys {
+            key.healthy = true;
+            key.cooldown_until = None;
+        }
+    }
+
+    pub(crate) fn has_available(&self) -> bool {
+        let now = Instant::now();
+        self.lock().keys.iter().any(|key| key.is_available(now))
+    }
+
+    /// Picks the key for the next request and counts the request against it.
+    pub(crate) fn select(&self) -> (usize, String) {
+        let now = Instant::now();
+        let mut state = self.lock();
+        let len = state.keys.len();
+        let available = |index: &usize| state.keys[*index].is_available(now);
+        let selected = match self.strategy {
+            RotationStrategy::RoundRobin => {
+                (0..len).map(|offset| (state.next + offset) % len).find(available)
+            }
+            RotationStrategy::LeastUsed => {
+                (0..len).filter(available).min_by_key(|index| state.keys[*index].requests)
+            }
+            RotationStrategy::RemainingQuota => {
+                (0..len).filter(available).max_by_key(|index| state.keys[*index].remaining_quota())
+            }
+        };
+        // With no key available, send anyway so the caller sees the API's own error, preferring
+        // the healthy key whose cooldown ends first.
+        let index = selected
+            .or_else(|| {
+                (0..len)
+                    .filter(|index| state.keys[*index].healthy)
+                    .min_by_key(|index| state.keys[*index].cooldown_until)
+            })
+            .unwrap_or(state.next % len);
+        state.next = (index + 1) % len;
+        let key = &mut state.keys[index];
+        key.requests += 1;
+        (index, key.key.clone())
+    }
+
+    pub(crate) fn record_response(
+        &self,
+        index: usize,
+        status: StatusCode,
+        retry_after: Option<Duration>,
+        rate_limit: Option<&RateLimitInfo>
+    ) {
+        let cooldown = self.cooldown;
+        let mut state = self.lock();
+        let Some(key) = state.keys.get_mut(index) else {
+            return;
+        };
+        if let Some(rate_limit) = rate_limit {
+            key.rate_limit = Some(rate_limit.clone());
+        }
+        if status.is_success() {
+            key.successes += 1;
+            return;
+        }
+        key.failures += 1;
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                key.healthy = false;
+            }
+            StatusCode::TOO_MANY_REQUESTS => {
+                key.rate_limited += 1;
+                let delay = retry_after.or_else(|| rate_limit.and_then(RateLimitInfo::reset_delay));
+                key.cooldown_until = Some(Instant::now() + delay.unwrap_or(cooldown));
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn record_error(&self, index: usize) {
+        if let Some(key) = self.lock().keys.get_mut(index) {
+            key.failures += 1;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, PoolState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_pool_rotation_strategies() {
+        let round_robin = ApiKeyPool::new(["key-a", "key-b"], RotationStrategy::default()).unwrap();
+        let selected: Vec<usize> = (0..3).map(|_| round_robin.select().0).collect();
+        assert_eq!(selected, [0, 1, 0]);
+
+        let least_used = ApiKeyPool::new(["key-a", "key-b"], RotationStrategy::LeastUsed).unwrap();
+        least_used.select();
+        assert_eq!(least_used.select().0, 1);
+
+        let quota = ApiKeyPool::new(["key-a", "key-b"], RotationStrategy::RemainingQuota).unwrap();
+        let info = |remaining| RateLimitInfo {
+            windows: vec![crate::types::RateLimitWindow {
+                limit: 1000,
+                window: Some(Duration::from_secs(2_592_000)),
+                remaining,
+                reset: Duration::from_secs(3600),
+            }],
+        };
+        quota.record_response(0, StatusCode::OK, None, Some(&info(10)));
+        quota.record_response(1, StatusCode::OK, None, Some(&info(900)));
+        assert_eq!(quota.select().0, 1);
+    }
+
+    #[test]
+    fn test_key_pool_skips_rejected_and_rate_limited_keys() {
+        let pool = ApiKeyPool::new(["key-a", "key-b", "key-c"], RotationStrategy::RoundRobin)
+            .unwrap()
+            .with_cooldown(Duration::from_secs(30));
+
+        pool.record_response(0, StatusCode::UNAUTHORIZED, None, None);
+        pool.record_response(1, StatusCode::TOO_MANY_REQUESTS, None, None);
+
+        assert_eq!(pool.select(), (2, String::from("key-c")));
+        pool.record_response(2, StatusCode::FORBIDDEN, None, None);
+        assert!(!pool.has_available());
+        assert_eq!(pool.select().0, 1);
+
+        let stats = pool.stats();
+        assert_eq!(stats[0].key_suffix, "ey-a");
+        assert!(!stats[0].healthy);
+        assert_eq!(stats[1].rate_limited, 1);
+        assert!(stats[1].cooldown.is_some_and(|cooldown| cooldown <= Duration::from_secs(30)));
+        assert_eq!((stats[2].requests, stats[2].failures), (1, 1));
+
+        pool.reset_health();
+        assert!(pool.has_available());
+    }
+}
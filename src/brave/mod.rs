@@ -1,6 +1,21 @@
 pub mod brave_client;
+pub mod cache;
+pub mod client_builder;
+mod coalesce;
 pub mod query_builders;
+pub mod query_encoder;
 pub mod errors;
+pub mod key_pool;
+pub mod rate_limiter;
+pub mod retry;
+pub mod stats;
+mod sse;
 
 pub use brave_client::{ BraveClient };
+pub use cache::{ CacheMode, CacheStatus, CachedResponse, DiskCache, MemoryCache, SearchCache };
+pub use client_builder::BraveClientBuilder;
 pub use errors::BraveClientError;
+pub use key_pool::{ ApiKeyPool, KeyStats, RotationStrategy };
+pub use rate_limiter::RateLimiter;
+pub use retry::{ RetryPolicy, RetryPolicyBuilder };
+pub use stats::ClientStats;
@@ -1,42 +1,240 @@
-use crate::types::WebSearchQueryParams;
+use serde::Serialize;
 
-impl From<WebSearchQueryParams> for String {
-    fn from(params: WebSearchQueryParams) -> Self {
-        web_search_query_builder(&params).unwrap_or_default()
-    }
+use crate::brave::{ query_encoder::encode_query, BraveClientError };
+use crate::types::{
+    ImageSearchQueryParams,
+    NewsSearchQueryParams,
+    SpellcheckQueryParams,
+    SuggestQueryParams,
+    VideoSearchQueryParams,
+    WebSearchQueryParams,
+};
+
+macro_rules! impl_into_query_string {
+    ($($params:ty => $builder:ident),+) => {
+        $(
+            impl From<$params> for String {
+                /// Encodes the endpoint path and query, or an empty string when there are no
+                /// params or they fail to encode. Use the query builder to see encoding errors.
+                fn from(params: $params) -> Self {
+                    $builder(&params).ok().flatten().unwrap_or_default()
+                }
+            }
+        )+
+    };
 }
 
-pub fn web_search_query_builder(params: &WebSearchQueryParams) -> Option<String> {
-    let mut params_vec = vec![];
+impl_into_query_string!(
+    WebSearchQueryParams => web_search_query_builder,
+    ImageSearchQueryParams => image_search_query_builder,
+    NewsSearchQueryParams => news_search_query_builder,
+    VideoSearchQueryParams => video_search_query_builder,
+    SuggestQueryParams => suggest_query_builder,
+    SpellcheckQueryParams => spellcheck_query_builder
+);
 
-    if let Some(q) = &params.q {
-        params_vec.push(format!("q={}", urlencoding::encode(q)));
-    }
-    if let Some(count) = params.count {
-        params_vec.push(format!("count={}", count));
+pub fn web_search_query_builder(params: &WebSearchQueryParams) -> Result<Option<String>, BraveClientError> {
+    endpoint_query("/web/search", params)
+}
+
+pub fn image_search_query_builder(params: &ImageSearchQueryParams) -> Result<Option<String>, BraveClientError> {
+    endpoint_query("/images/search", params)
+}
+
+pub fn news_search_query_builder(params: &NewsSearchQueryParams) -> Result<Option<String>, BraveClientError> {
+    endpoint_query("/news/search", params)
+}
+
+pub fn video_search_query_builder(params: &VideoSearchQueryParams) -> Result<Option<String>, BraveClientError> {
+    endpoint_query("/videos/search", params)
+}
+
+pub fn suggest_query_builder(params: &SuggestQueryParams) -> Result<Option<String>, BraveClientError> {
+    endpoint_query("/suggest/search", params)
+}
+
+pub fn spellcheck_query_builder(params: &SpellcheckQueryParams) -> Result<Option<String>, BraveClientError> {
+    endpoint_query("/spellcheck/search", params)
+}
+
+/// Returns `None` when no params are set.
+fn endpoint_query<T: Serialize>(
+    path: &str,
+    params: &T
+) -> Result<Option<String>, BraveClientError> {
+    let query = encode_query(params)?;
+    Ok((!query.is_empty()).then(|| format!("{}?{}", path, query)))
+}
+
+pub fn summarizer_query_builder(key: &str) -> String {
+    format!("/summarizer/search?key={}&entity_info=1", urlencoding::encode(key))
+}
+
+pub fn rich_query_builder(callback_key: &str) -> String {
+    format!("/web/rich?callback_key={}", urlencoding::encode(callback_key))
+}
+
+pub fn local_pois_query_builder(ids: &[String]) -> String {
+    format!("/local/pois?{}", ids_query(ids))
+}
+
+pub fn local_descriptions_query_builder(ids: &[String]) -> String {
+    format!("/local/descriptions?{}", ids_query(ids))
+}
+
+fn ids_query(ids: &[String]) -> String {
+    #[derive(Serialize)]
+    struct Ids<'a> {
+        ids: &'a [String],
     }
-    if let Some(offset) = params.offset {
-        params_vec.push(format!("offset={}", offset));
+
+    encode_query(&Ids { ids }).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::query_params::{
+        NewsSearchQueryParamsBuilder,
+        SuggestQueryParamsBuilder,
+        WebSearchQueryParamsBuilder,
+    };
+    use crate::types::{
+        Country,
+        Date,
+        Freshness,
+        ResultSection,
+        SafeSearch,
+        SearchLang,
+        UiLang,
+        Units,
+    };
+
+    #[test]
+    fn test_web_search_query_builder_encodes_all_params() {
+        let params = WebSearchQueryParamsBuilder::default()
+            .q("rust async runtime")
+            .count(10u32)
+            .offset(2u32)
+            .search_lang(SearchLang::En)
+            .ui_lang(UiLang::EnUs)
+            .safesearch(SafeSearch::Moderate)
+            .freshness(Freshness::PastWeek)
+            .country(Country::Us)
+            .text_decorations(false)
+            .spellcheck(true)
+            .result_filter([ResultSection::News, ResultSection::Web])
+            .units(Units::Metric)
+            .extra_snippets(true)
+            .summary(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            web_search_query_builder(&params).unwrap().unwrap(),
+            "/web/search?q=rust%20async%20runtime&count=10&offset=2&search_lang=en&ui_lang=en-US\
+            &safesearch=moderate&freshness=pw&country=US&text_decorations=0&spellcheck=1\
+            &result_filter=news,web&units=metric&extra_snippets=true&summary=1"
+        );
     }
-    if let Some(search_lang) = &params.search_lang {
-        params_vec.push(format!("search_lang={}", urlencoding::encode(search_lang)));
+
+    #[test]
+    fn test_web_search_query_builder_renders_freshness_range() {
+        let freshness = Freshness::range(
+            Date::new(2025, 1, 1).unwrap(),
+            Date::new(2025, 6, 30).unwrap()
+        ).unwrap();
+        let params = WebSearchQueryParamsBuilder::default()
+            .q("rust")
+            .freshness(freshness)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            web_search_query_builder(&params).unwrap().unwrap(),
+            "/web/search?q=rust&freshness=2025-01-01to2025-06-30"
+        );
     }
-    if let Some(safesearch) = &params.safesearch {
-        params_vec.push(format!("safesearch={}", urlencoding::encode(safesearch)));
+
+    #[test]
+    fn test_web_search_query_builder_skips_unset_params() {
+        let params = WebSearchQueryParamsBuilder::default()
+            .q("rust")
+            .ui_lang(UiLang::DeDe)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            web_search_query_builder(&params).unwrap().as_deref(),
+            Some("/web/search?q=rust&ui_lang=de-DE")
+        );
+        assert_eq!(web_search_query_builder(&WebSearchQueryParams::default()).unwrap(), None);
     }
-    if let Some(freshness) = &params.freshness {
-        params_vec.push(format!("freshness={}", urlencoding::encode(freshness)));
+
+    #[test]
+    fn test_web_search_query_builder_encodes_goggles() {
+        let params = WebSearchQueryParamsBuilder::default()
+            .q("rust")
+            .goggles_id("https://example.com/tech.goggle")
+            .goggles(
+                vec![
+                    "https://example.com/blogs.goggle".to_string(),
+                    "$boost=3,site=docs.rs\n$discard,site=pinterest.com".to_string()
+                ]
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            web_search_query_builder(&params).unwrap().unwrap(),
+            "/web/search?q=rust\
+            &goggles_id=https%3A%2F%2Fexample.com%2Ftech.goggle\
+            &goggles=https%3A%2F%2Fexample.com%2Fblogs.goggle\
+            &goggles=%24boost%3D3%2Csite%3Ddocs.rs%0A%24discard%2Csite%3Dpinterest.com"
+        );
     }
-    if let Some(country) = &params.country {
-        params_vec.push(format!("country={}", urlencoding::encode(country)));
+
+    #[test]
+    fn test_params_into_string_encodes_path_and_query() {
+        let web: String = WebSearchQueryParamsBuilder::default()
+            .q("rust")
+            .count(5u32)
+            .extra_params(vec![("operators", "false")])
+            .build()
+            .unwrap()
+            .into();
+        let news: String = NewsSearchQueryParamsBuilder::default()
+            .q("rust")
+            .spellcheck(false)
+            .extra_snippets(true)
+            .build()
+            .unwrap()
+            .into();
+        let suggest: String = SuggestQueryParamsBuilder::default()
+            .q("rus")
+            .rich(true)
+            .build()
+            .unwrap()
+            .into();
+
+        assert_eq!(web, "/web/search?q=rust&count=5&operators=false");
+        assert_eq!(news, "/news/search?q=rust&spellcheck=0&extra_snippets=true");
+        assert_eq!(suggest, "/suggest/search?q=rus&rich=true");
+        assert_eq!(String::from(WebSearchQueryParams::default()), "");
     }
-    if let Some(source) = &params.source {
-        params_vec.push(format!("source={}", urlencoding::encode(source)));
+
+    #[test]
+    fn test_endpoint_query_propagates_encoding_errors() {
+        assert!(matches!(
+            endpoint_query("/web/search", &["rust"]),
+            Err(BraveClientError::InvalidParameter(_))
+        ));
     }
 
-    if params_vec.is_empty() {
-        None
-    } else {
-        Some(format!("/web/search?{}", params_vec.join("&")))
+    #[test]
+    fn test_local_ids_repeat_key() {
+        let ids = vec!["a b".to_string(), "c".to_string()];
+
+        assert_eq!(local_pois_query_builder(&ids), "/local/pois?ids=a%20b&ids=c");
     }
 }
@@ -0,0 +1,501 @@
+use std::fmt::Display;
+
+use serde::{ ser::{ self, Impossible }, Serialize, Serializer };
+
+use crate::brave::BraveClientError;
+
+/// Encodes a struct or map into a URL query string (`a=1&b=2`).
+///
+/// `None` fields are skipped, bools render as `true`/`false`, enums render through their
+/// `Serialize` impls and sequences repeat the key once per element. Fields that the API expects
+/// as a comma-joined list or a `0`/`1` flag opt in with [`comma_separated`] and [`flag`].
+pub fn encode_query<T: Serialize + ?Sized>(params: &T) -> Result<String, BraveClientError> {
+    let mut encoder = QueryEncoder { pairs: vec![] };
+    params.serialize(&mut encoder)?;
+    let pairs: Vec<String> = encoder.pairs
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect();
+    Ok(pairs.join("&"))
+}
+
+/// Serializes a list as a single comma-joined value, e.g. `result_filter=web,news`. Each
+/// element is percent-encoded on its own; other serializers see a plain sequence.
+pub fn comma_separated<S: Serializer, T: Serialize>(
+    values: &Option<Vec<T>>,
+    serializer: S
+) -> Result<S::Ok, S::Error> {
+    match values {
+        Some(values) => serialize_comma_separated(values, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Like [`comma_separated`], for `Serialize` impls of list-like types.
+pub fn serialize_comma_separated<S: Serializer, T: Serialize>(
+    values: &[T],
+    serializer: S
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_newtype_struct(COMMA_SEPARATED, values)
+}
+
+/// Serializes a bool as `1`/`0` for params the API documents as numeric flags.
+pub fn flag<S: Serializer>(value: &Option<bool>, serializer: S) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serializer.serialize_u8(*value as u8),
+        None => serializer.serialize_none(),
+    }
+}
+
+const COMMA_SEPARATED: &str = "$brave::comma_separated";
+
+impl ser::Error for BraveClientError {
+    fn custom<T: Display>(msg: T) -> Self {
+        BraveClientError::InvalidParameter(msg.to_string())
+    }
+}
+
+fn unsupported(what: &str) -> BraveClientError {
+    BraveClientError::InvalidParameter(format!("query params cannot contain {}", what))
+}
+
+/// Collects percent-encoded `(key, value)` pairs.
+struct QueryEncoder {
+    pairs: Vec<(String, String)>,
+}
+
+impl QueryEncoder {
+    fn push<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), BraveClientError> {
+        for value in value.serialize(ValueSerializer)? {
+            self.pairs.push((key.to_string(), value));
+        }
+        Ok(())
+    }
+}
+
+macro_rules! top_level_unsupported {
+    ($($method:ident($($arg:ty),*)),+ $(,)?) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> Result<Self::Ok, Self::Error> {
+                Err(unsupported("a top-level value other than a struct or map"))
+            }
+        )+
+    };
+}
+
+impl<'a> Serializer for &'a mut QueryEncoder {
+    type Ok = ();
+    type Error = BraveClientError;
+    type SerializeSeq = Impossible<(), BraveClientError>;
+    type SerializeTuple = Impossible<(), BraveClientError>;
+    type SerializeTupleStruct = Impossible<(), BraveClientError>;
+    type SerializeTupleVariant = Impossible<(), BraveClientError>;
+    type SerializeMap = MapEncoder<'a>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<(), BraveClientError>;
+
+    top_level_unsupported!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+        serialize_none(),
+        serialize_unit(),
+        serialize_unit_struct(&'static str),
+        serialize_unit_variant(&'static str, u32, &'static str),
+    );
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), BraveClientError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T
+    ) -> Result<(), BraveClientError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T
+    ) -> Result<(), BraveClientError> {
+        Err(unsupported("a top-level enum"))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, BraveClientError> {
+        Err(unsupported("a top-level sequence"))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, BraveClientError> {
+        Err(unsupported("a top-level tuple"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize
+    ) -> Result<Self::SerializeTupleStruct, BraveClientError> {
+        Err(unsupported("a top-level tuple struct"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize
+    ) -> Result<Self::SerializeTupleVariant, BraveClientError> {
+        Err(unsupported("a top-level enum"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, BraveClientError> {
+        Ok(MapEncoder { encoder: self, key: None })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize
+    ) -> Result<Self::SerializeStruct, BraveClientError> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize
+    ) -> Result<Self::SerializeStructVariant, BraveClientError> {
+        Err(unsupported("a top-level enum"))
+    }
+}
+
+impl ser::SerializeStruct for &mut QueryEncoder {
+    type Ok = ();
+    type Error = BraveClientError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T
+    ) -> Result<(), BraveClientError> {
+        self.push(&urlencoding::encode(key), value)
+    }
+
+    fn end(self) -> Result<(), BraveClientError> {
+        Ok(())
+    }
+}
+
+struct MapEncoder<'a> {
+    encoder: &'a mut QueryEncoder,
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for MapEncoder<'_> {
+    type Ok = ();
+    type Error = BraveClientError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), BraveClientError> {
+        let mut values = key.serialize(ValueSerializer)?;
+        if values.len() != 1 {
+            return Err(unsupported("a map key that is not a single value"));
+        }
+        self.key = values.pop();
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T
+    ) -> Result<(), BraveClientError> {
+        let key = self.key.take().ok_or_else(|| unsupported("a map value without a key"))?;
+        self.encoder.push(&key, value)
+    }
+
+    fn end(self) -> Result<(), BraveClientError> {
+        Ok(())
+    }
+}
+
+/// Serializes a single field into zero (`None`), one (scalars) or many (sequences)
+/// percent-encoded values.
+struct ValueSerializer;
+
+macro_rules! value_to_string {
+    ($($method:ident($ty:ty)),+ $(,)?) => {
+        $(
+            fn $method(self, value: $ty) -> Result<Self::Ok, Self::Error> {
+                Ok(vec![urlencoding::encode(&value.to_string()).into_owned()])
+            }
+        )+
+    };
+}
+
+impl Serializer for ValueSerializer {
+    type Ok = Vec<String>;
+    type Error = BraveClientError;
+    type SerializeSeq = SeqValues;
+    type SerializeTuple = SeqValues;
+    type SerializeTupleStruct = SeqValues;
+    type SerializeTupleVariant = Impossible<Vec<String>, BraveClientError>;
+    type SerializeMap = Impossible<Vec<String>, BraveClientError>;
+    type SerializeStruct = Impossible<Vec<String>, BraveClientError>;
+    type SerializeStructVariant = Impossible<Vec<String>, BraveClientError>;
+
+    value_to_string!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+    );
+
+    fn serialize_bytes(self, _value: &[u8]) -> Result<Vec<String>, BraveClientError> {
+        Err(unsupported("bytes"))
+    }
+
+    fn serialize_none(self) -> Result<Vec<String>, BraveClientError> {
+        Ok(vec![])
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(
+        self,
+        value: &T
+    ) -> Result<Vec<String>, BraveClientError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Vec<String>, BraveClientError> {
+        Ok(vec![])
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Vec<String>, BraveClientError> {
+        Ok(vec![])
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str
+    ) -> Result<Vec<String>, BraveClientError> {
+        Ok(vec![urlencoding::encode(variant).into_owned()])
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T
+    ) -> Result<Vec<String>, BraveClientError> {
+        let values = value.serialize(self)?;
+        if name == COMMA_SEPARATED && !values.is_empty() {
+            return Ok(vec![values.join(",")]);
+        }
+        Ok(values)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T
+    ) -> Result<Vec<String>, BraveClientError> {
+        Err(unsupported("an enum variant with data"))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqValues, BraveClientError> {
+        Ok(SeqValues { values: Vec::with_capacity(len.unwrap_or_default()) })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqValues, BraveClientError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize
+    ) -> Result<SeqValues, BraveClientError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize
+    ) -> Result<Self::SerializeTupleVariant, BraveClientError> {
+        Err(unsupported("an enum variant with data"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, BraveClientError> {
+        Err(unsupported("a nested map"))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize
+    ) -> Result<Self::SerializeStruct, BraveClientError> {
+        Err(unsupported("a nested struct"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize
+    ) -> Result<Self::SerializeStructVariant, BraveClientError> {
+        Err(unsupported("an enum variant with data"))
+    }
+}
+
+struct SeqValues {
+    values: Vec<String>,
+}
+
+impl SeqValues {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), BraveClientError> {
+        let mut values = value.serialize(ValueSerializer)?;
+        if values.len() > 1 {
+            return Err(unsupported("nested sequences"));
+        }
+        self.values.append(&mut values);
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for SeqValues {
+    type Ok = Vec<String>;
+    type Error = BraveClientError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T
+    ) -> Result<(), BraveClientError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Vec<String>, BraveClientError> {
+        Ok(self.values)
+    }
+}
+
+impl ser::SerializeTuple for SeqValues {
+    type Ok = Vec<String>;
+    type Error = BraveClientError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T
+    ) -> Result<(), BraveClientError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Vec<String>, BraveClientError> {
+        Ok(self.values)
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqValues {
+    type Ok = Vec<String>;
+    type Error = BraveClientError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T
+    ) -> Result<(), BraveClientError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Vec<String>, BraveClientError> {
+        Ok(self.values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Mode {
+        Fast,
+    }
+
+    #[derive(Serialize)]
+    struct Params {
+        q: Option<String>,
+        skipped: Option<u32>,
+        enabled: Option<bool>,
+        #[serde(serialize_with = "flag")]
+        numeric: Option<bool>,
+        mode: Option<Mode>,
+        #[serde(serialize_with = "comma_separated")]
+        filters: Option<Vec<String>>,
+        repeated: Option<Vec<String>>,
+        #[serde(flatten)]
+        extra: BTreeMap<String, String>,
+    }
+
+    #[test]
+    fn test_encode_query() {
+        let params = Params {
+            q: Some("rust & tokio".to_string()),
+            skipped: None,
+            enabled: Some(false),
+            numeric: Some(true),
+            mode: Some(Mode::Fast),
+            filters: Some(vec!["web".to_string(), "news".to_string()]),
+            repeated: Some(vec!["a b".to_string(), "c".to_string()]),
+            extra: BTreeMap::from([("custom".to_string(), "1".to_string())]),
+        };
+
+        assert_eq!(
+            encode_query(&params).unwrap(),
+            "q=rust%20%26%20tokio&enabled=false&numeric=1&mode=fast&filters=web,news\
+            &repeated=a%20b&repeated=c&custom=1"
+        );
+    }
+
+    #[test]
+    fn test_encode_query_rejects_nested_values() {
+        #[derive(Serialize)]
+        struct Nested {
+            inner: Option<Vec<Vec<u32>>>,
+        }
+
+        assert!(encode_query(&Nested { inner: Some(vec![vec![1, 2]]) }).is_err());
+        assert!(encode_query(&42u32).is_err());
+    }
+}
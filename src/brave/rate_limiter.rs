@@ -0,0 +1,173 @@
+use std::time::{ Duration, Instant };
+
+use tokio::sync::Mutex;
+
+use crate::{ brave::BraveClientError, types::RateLimitInfo };
+
+const SECOND: Duration = Duration::from_secs(1);
+/// Brave's monthly quota window, as reported by `X-RateLimit-Policy`.
+const MONTH: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Client-side token bucket that delays requests to stay within a plan's limits, so concurrent
+/// tasks wait their turn instead of receiving 429s. Waiters are served in arrival order.
+#[derive(Debug)]
+pub struct RateLimiter {
+    state: Mutex<LimiterState>,
+    max_wait: Duration,
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    buckets: Vec<Bucket>,
+    refilled_at: Instant,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    window: Duration,
+    capacity: f64,
+    tokens: f64,
+}
+
+impl Bucket {
+    fn new(limit: u64, window: Duration) -> Self {
+        Bucket { window, capacity: limit as f64, tokens: limit as f64 }
+    }
+
+    fn refill(&mut self, elapsed: Duration) {
+        let refilled = elapsed.as_secs_f64() * self.capacity / self.window.as_secs_f64();
+        self.tokens = (self.tokens + refilled).min(self.capacity);
+    }
+
+    fn wait(&self) -> Duration {
+        if self.tokens >= 1.0 {
+            return Duration::ZERO;
+        }
+        self.window.mul_f64((1.0 - self.tokens) / self.capacity)
+    }
+}
+
+impl RateLimiter {
+    /// Limits requests to `per_second`, and to `per_month` when the plan has a monthly quota.
+    pub fn new(per_second: u32, per_month: Option<u64>) -> Result<Self, BraveClientError> {
+        if per_second == 0 || per_month == Some(0) {
+            return Err(BraveClientError::InvalidParameter("rate limits must be at least 1".into()));
+        }
+        let mut buckets = vec![Bucket::new(per_second.into(), SECOND)];
+        buckets.extend(per_month.map(|per_month| Bucket::new(per_month, MONTH)));
+        Ok(RateLimiter {
+            state: Mutex::new(LimiterState { buckets, refilled_at: Instant::now() }),
+            max_wait: Duration::from_secs(60),
+        })
+    }
+
+    /// Longest a request waits for capacity before failing with `RateLimited`, e.g. once the
+    /// monthly quota is used up. Defaults to one minute.
+    pub fn with_max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = max_wait;
+        self
+    }
+
+    /// Reserves one request from every window, then waits until the reservation is due. Tokens
+    /// are reserved under the lock, so later callers queue behind earlier ones without holding
+    /// it while they sleep.
+    pub async fn acquire(&self) -> Result<(), BraveClientError> {
+        let wait = {
+            let mut state = self.state.lock().await;
+            state.refill();
+            let wait = state.buckets.iter().map(Bucket::wait).max().unwrap_or_default();
+            if wait > self.max_wait {
+                return Err(BraveClientError::RateLimited(wait));
+            }
+            state.buckets.iter_mut().for_each(|bucket| bucket.tokens -= 1.0);
+            wait
+        };
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+        Ok(())
+    }
+
+    /// Lowers the available capacity to what the server reports as remaining, so requests made
+    /// elsewhere with the same key are accounted for.
+    pub async fn observe(&self, info: &RateLimitInfo) {
+        let mut state = self.state.lock().await;
+        for reported in &info.windows {
+            let window = reported.window;
+            if let Some(bucket) = state.buckets.iter_mut().find(|bucket| Some(bucket.window) == window) {
+                bucket.tokens = bucket.tokens.min(reported.remaining as f64);
+            }
+        }
+    }
+}
+
+impl LimiterState {
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at);
+        self.buckets.iter_mut().for_each(|bucket| bucket.refill(elapsed));
+        self.refilled_at = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::types::RateLimitWindow;
+
+    #[tokio::test]
+    async fn test_rate_limiter_makes_concurrent_tasks_wait() {
+        let limiter = Arc::new(RateLimiter::new(10, None).unwrap());
+        let started = Instant::now();
+
+        let tasks: Vec<_> = (0..13)
+            .map(|_| {
+                let limiter = limiter.clone();
+                tokio::spawn(async move { limiter.acquire().await })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+
+        // 10 requests fit the burst, the remaining 3 wait 100ms each.
+        assert!(started.elapsed() >= Duration::from_millis(250));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_observe_does_not_wait_behind_sleepers() {
+        let limiter = Arc::new(RateLimiter::new(1, None).unwrap());
+        limiter.acquire().await.unwrap();
+        let waiting = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire().await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let started = Instant::now();
+        limiter.observe(&RateLimitInfo { windows: vec![] }).await;
+
+        assert!(started.elapsed() < Duration::from_millis(100));
+        waiting.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_fails_once_monthly_quota_is_used_up() {
+        let limiter = RateLimiter::new(10, Some(2)).unwrap();
+        limiter.acquire().await.unwrap();
+        limiter.observe(
+            &(RateLimitInfo {
+                windows: vec![RateLimitWindow {
+                    limit: 2,
+                    window: Some(MONTH),
+                    remaining: 0,
+                    reset: MONTH,
+                }],
+            })
+        ).await;
+
+        assert!(matches!(limiter.acquire().await, Err(BraveClientError::RateLimited(_))));
+        assert!(RateLimiter::new(0, None).is_err());
+    }
+}
@@ -0,0 +1,233 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{ BuildHasher, Hasher },
+    time::{ Duration, SystemTime },
+};
+
+use derive_builder::Builder;
+use reqwest::{ header::HeaderMap, StatusCode };
+
+use crate::{ brave::BraveClientError, types::RateLimitInfo };
+
+/// When and how long the client waits before retrying a failed request.
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(setter(into), build_fn(private, name = "fallible_build"))]
+pub struct RetryPolicy {
+    /// Total attempts, including the first request.
+    #[builder(default = "3")]
+    pub max_attempts: u32,
+    #[builder(default = "Duration::from_millis(500)")]
+    pub initial_backoff: Duration,
+    #[builder(default = "Duration::from_secs(30)")]
+    pub max_backoff: Duration,
+    #[builder(default = "2.0")]
+    pub multiplier: f64,
+    /// Randomizes each backoff between half and all of its value so concurrent clients spread
+    /// out their retries.
+    #[builder(default = "true")]
+    pub jitter: bool,
+    #[builder(default = "vec![429, 500, 502, 503, 504]")]
+    pub retryable_statuses: Vec<u16>,
+    #[builder(default = "true")]
+    pub retry_connect_errors: bool,
+    #[builder(default = "true")]
+    pub retry_timeouts: bool,
+    /// Longest `Retry-After` or `X-RateLimit-Reset` wait to honor. Responses asking for a longer
+    /// wait are returned as errors instead of retried.
+    #[builder(default = "Duration::from_secs(60)")]
+    pub max_retry_after: Duration,
+}
+
+impl RetryPolicyBuilder {
+    pub fn build(&self) -> Result<RetryPolicy, BraveClientError> {
+        let policy = self
+            .fallible_build()
+            .map_err(|err| BraveClientError::InvalidParameter(err.to_string()))?;
+        if policy.max_attempts == 0 {
+            return Err(BraveClientError::InvalidParameter("max_attempts must be at least 1".into()));
+        }
+        if !policy.multiplier.is_finite() || policy.multiplier < 1.0 {
+            return Err(BraveClientError::InvalidParameter("multiplier must be at least 1".into()));
+        }
+        Ok(policy)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicyBuilder::default().fallible_build().unwrap_or_else(|_| RetryPolicy::none())
+    }
+}
+
+impl RetryPolicy {
+    /// Sends every request once. This is the client's default.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+            multiplier: 1.0,
+            jitter: false,
+            retryable_statuses: vec![],
+            retry_connect_errors: false,
+            retry_timeouts: false,
+            max_retry_after: Duration::ZERO,
+        }
+    }
+
+    /// Backoff before the `retry`th retry (starting at 1), before jitter.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.powi(retry.saturating_sub(1).min(i32::MAX as u32) as i32);
+        // Clamp in seconds first, as the unclamped product overflows `Duration` for large retries.
+        let seconds = self.initial_backoff.as_secs_f64() * factor;
+        Duration::try_from_secs_f64(seconds.min(self.max_backoff.as_secs_f64()))
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+
+    pub(crate) fn is_retryable_status(&self, status: StatusCode) -> bool {
+        self.retryable_statuses.contains(&status.as_u16())
+    }
+
+    pub(crate) fn is_retryable_error(&self, err: &reqwest::Error) -> bool {
+        (self.retry_connect_errors && err.is_connect()) || (self.retry_timeouts && err.is_timeout())
+    }
+
+    /// Delay before retrying a transport error.
+    pub(crate) fn error_delay(&self, retry: u32) -> Duration {
+        self.jittered(self.backoff(retry))
+    }
+
+    /// Delay before retrying a response with a retryable status, preferring the server's
+    /// `Retry-After` or, when rate limited, `X-RateLimit-Reset` hint. `None` when the server asks
+    /// for a longer wait than `max_retry_after`.
+    pub(crate) fn response_delay(
+        &self,
+        retry: u32,
+        status: StatusCode,
+        headers: &HeaderMap
+    ) -> Option<Duration> {
+        match server_delay(status, headers) {
+            Some(delay) if delay > self.max_retry_after => None,
+            Some(delay) => Some(delay),
+            None => Some(self.jittered(self.backoff(retry))),
+        }
+    }
+
+    fn jittered(&self, delay: Duration) -> Duration {
+        if !self.jitter || delay.is_zero() {
+            return delay;
+        }
+        let random = RandomState::new().build_hasher().finish();
+        let half = delay / 2;
+        half + half.mul_f64((random as f64) / (u64::MAX as f64))
+    }
+}
+
+/// Reads the wait requested by `Retry-After`, falling back to `X-RateLimit-Reset` when the
+/// request was rate limited. Brave sends the rate limit headers on every response, so other
+/// failures use the backoff instead of waiting for the next window.
+fn server_delay(status: StatusCode, headers: &HeaderMap) -> Option<Duration> {
+    retry_after(headers).or_else(|| {
+        let info = RateLimitInfo::from_headers(headers)?;
+        if status != StatusCode::TOO_MANY_REQUESTS && !info.is_exhausted() {
+            return None;
+        }
+        info.reset_delay()
+    })
+}
+
+/// Parses `Retry-After` as seconds or an HTTP date.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let retry_after = headers.get("Retry-After")?.to_str().ok()?.trim();
+    if let Ok(seconds) = retry_after.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(retry_after).ok()?;
+    Some(date.duration_since(SystemTime::now()).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_backoff_grows_exponentially_up_to_max() {
+        let policy = RetryPolicyBuilder::default()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(350))
+            .jitter(false)
+            .build()
+            .unwrap();
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+        assert_eq!(policy.error_delay(2), Duration::from_millis(200));
+
+        let jittered = RetryPolicy { jitter: true, ..policy }.error_delay(2);
+        assert!((Duration::from_millis(100)..=Duration::from_millis(200)).contains(&jittered));
+        assert_eq!(RetryPolicy::default().backoff(80), Duration::from_secs(30));
+        assert_eq!(RetryPolicy::default().backoff(u32::MAX), Duration::from_secs(30));
+        assert!(RetryPolicyBuilder::default().max_attempts(0u32).build().is_err());
+        assert!(RetryPolicyBuilder::default().multiplier(f64::NAN).build().is_err());
+        assert!(RetryPolicyBuilder::default().multiplier(f64::INFINITY).build().is_err());
+    }
+
+    #[test]
+    fn test_response_delay_honors_server_hints() {
+        let policy = RetryPolicyBuilder::default().jitter(false).build().unwrap();
+        let retry_at = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(3));
+
+        assert_eq!(
+            policy.response_delay(1, StatusCode::SERVICE_UNAVAILABLE, &headers(&[("Retry-After", "7")])),
+            Some(Duration::from_secs(7))
+        );
+        assert!(
+            policy
+                .response_delay(1, StatusCode::SERVICE_UNAVAILABLE, &headers(&[("Retry-After", &retry_at)]))
+                .is_some_and(|delay| delay <= Duration::from_secs(3))
+        );
+        assert_eq!(
+            policy.response_delay(
+                1,
+                StatusCode::TOO_MANY_REQUESTS,
+                &headers(&[("X-RateLimit-Reset", "1, 1419704"), ("X-RateLimit-Remaining", "0, 9000")])
+            ),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            policy.response_delay(
+                1,
+                StatusCode::SERVICE_UNAVAILABLE,
+                &headers(&[("X-RateLimit-Reset", "1, 1419704"), ("X-RateLimit-Remaining", "5, 9000")])
+            ),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(
+            policy.response_delay(
+                3,
+                StatusCode::SERVICE_UNAVAILABLE,
+                &headers(&[("X-RateLimit-Reset", "1, 1419704"), ("X-RateLimit-Remaining", "5, 9000")])
+            ),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            policy.response_delay(
+                1,
+                StatusCode::SERVICE_UNAVAILABLE,
+                &headers(&[("X-RateLimit-Reset", "1, 1419704"), ("X-RateLimit-Remaining", "1, 0")])
+            ),
+            None
+        );
+        assert_eq!(policy.response_delay(2, StatusCode::SERVICE_UNAVAILABLE, &HeaderMap::new()), Some(Duration::from_secs(1)));
+    }
+}
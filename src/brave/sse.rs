@@ -0,0 +1,70 @@
+/// Incremental decoder for `text/event-stream` bodies. Bytes are buffered until an event is
+/// complete so multi-byte characters split across chunks decode correctly.
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    buffer: Vec<u8>,
+}
+
+impl SseDecoder {
+    /// Feeds a chunk of the body and returns the `data` payloads of every completed event.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = vec![];
+        while let Some((end, separator_len)) = find_event_end(&self.buffer) {
+            let event: Vec<u8> = self.buffer.drain(..end + separator_len).take(end).collect();
+            events.extend(event_data(&event));
+        }
+        events
+    }
+
+    /// Returns the `data` payload of a last event the body ended without terminating.
+    pub fn finish(&mut self) -> Option<String> {
+        event_data(&std::mem::take(&mut self.buffer))
+    }
+}
+
+fn event_data(event: &[u8]) -> Option<String> {
+    let event = String::from_utf8_lossy(event);
+    let data: Vec<&str> = event
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| data.strip_prefix(' ').unwrap_or(data))
+        .collect();
+    (!data.is_empty()).then(|| data.join("\n"))
+}
+
+fn find_event_end(buffer: &[u8]) -> Option<(usize, usize)> {
+    let lf = buffer
+        .windows(2)
+        .position(|window| window == b"\n\n")
+        .map(|end| (end, 2));
+    let crlf = buffer
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .map(|end| (end, 4));
+    [lf, crlf].into_iter().flatten().min_by_key(|(end, _)| *end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_decoder_handles_split_events() {
+        let mut decoder = SseDecoder::default();
+
+        assert!(decoder.push(b"data: {\"a\":").is_empty());
+        assert_eq!(decoder.push(b"1}\n\ndata: [DONE]\n\n"), vec!["{\"a\":1}", "[DONE]"]);
+        assert_eq!(decoder.push(b": keep-alive\r\n\r\ndata: caf\xc3"), Vec::<String>::new());
+        assert_eq!(decoder.push(b"\xa9\r\n\r\n"), vec!["caf\u{e9}"]);
+    }
+
+    #[test]
+    fn test_sse_decoder_flushes_unterminated_last_event() {
+        let mut decoder = SseDecoder::default();
+
+        assert!(decoder.push(b"data: {\"a\":1}\n").is_empty());
+        assert_eq!(decoder.finish().as_deref(), Some("{\"a\":1}"));
+        assert_eq!(decoder.finish(), None);
+    }
+}
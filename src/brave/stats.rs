@@ -0,0 +1,41 @@
+use std::sync::atomic::{ AtomicU64, Ordering };
+
+/// Counters shared by a client and its clones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClientStats {
+    /// HTTP requests sent, including retries.
+    pub requests: u64,
+    /// Responses served from the cache.
+    pub cache_hits: u64,
+    /// Calls that shared another call's in-flight request instead of sending their own.
+    pub coalesced: u64,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct StatsCounters {
+    requests: AtomicU64,
+    cache_hits: AtomicU64,
+    coalesced: AtomicU64,
+}
+
+impl StatsCounters {
+    pub(crate) fn snapshot(&self) -> ClientStats {
+        ClientStats {
+            requests: self.requests.load(Ordering::Relaxed),
+            cache_hits: self.cache_hits.load(Ordering::Relaxed),
+            coalesced: self.coalesced.load(Ordering::Relaxed),
+        }
+    }
+
+    pub(crate) fn record_request(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_coalesced(&self) {
+        self.coalesced.fetch_add(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,257 @@
+use derive_builder::Builder;
+use serde::{ Deserialize, Serialize };
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatRole {
+    System,
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: ChatRole,
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn system(content: impl Into<String>) -> Self {
+        ChatMessage { role: ChatRole::System, content: content.into() }
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        ChatMessage { role: ChatRole::User, content: content.into() }
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        ChatMessage { role: ChatRole::Assistant, content: content.into() }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct AnswerRequest {
+    #[builder(default = "String::from(\"brave\")")]
+    pub model: String,
+    pub messages: Vec<ChatMessage>,
+    #[builder(setter(skip))]
+    pub stream: bool,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_citations: Option<bool>,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_entities: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnswerResponse {
+    pub id: String,
+    pub object: Option<String>,
+    pub created: Option<u64>,
+    pub model: String,
+    pub choices: Vec<AnswerChoice>,
+    pub usage: Option<Usage>,
+}
+
+impl AnswerResponse {
+    /// The answer text of the first choice, with citation and usage tags removed.
+    pub fn text(&self) -> String {
+        self.deltas()
+            .into_iter()
+            .filter_map(|delta| {
+                match delta {
+                    AnswerDelta::Text(text) => Some(text),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    pub fn citations(&self) -> Vec<Citation> {
+        self.deltas()
+            .into_iter()
+            .filter_map(|delta| {
+                match delta {
+                    AnswerDelta::Citation(citation) => Some(citation),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    fn deltas(&self) -> Vec<AnswerDelta> {
+        self.choices
+            .first()
+            .map(|choice| parse_answer_content(&choice.message.content))
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnswerChoice {
+    pub index: u32,
+    pub message: ChatMessage,
+    pub finish_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Usage {
+    #[serde(alias = "X-Request-Tokens-In")]
+    pub prompt_tokens: Option<u64>,
+    #[serde(alias = "X-Request-Tokens-Out")]
+    pub completion_tokens: Option<u64>,
+    pub total_tokens: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Citation {
+    pub number: Option<u32>,
+    pub url: String,
+    pub favicon: Option<String>,
+    pub snippet: Option<String>,
+    pub start_index: Option<u32>,
+    pub end_index: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnswerChunk {
+    pub id: String,
+    pub model: Option<String>,
+    pub choices: Vec<AnswerChunkChoice>,
+    pub usage: Option<Usage>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnswerChunkChoice {
+    pub index: u32,
+    pub delta: AnswerChunkDelta,
+    pub finish_reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnswerChunkDelta {
+    pub role: Option<ChatRole>,
+    pub content: Option<String>,
+}
+
+/// A typed piece of a streamed answer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnswerDelta {
+    Text(String),
+    Citation(Citation),
+    Usage(Usage),
+}
+
+impl AnswerChunk {
+    pub fn deltas(&self) -> Vec<AnswerDelta> {
+        let mut parser = AnswerContentParser::default();
+        let mut deltas = self.parse_deltas(&mut parser);
+        deltas.extend(parser.finish());
+        deltas
+    }
+
+    /// Like `deltas`, but holds back tags that continue in a later chunk.
+    pub(crate) fn parse_deltas(&self, parser: &mut AnswerContentParser) -> Vec<AnswerDelta> {
+        let mut deltas: Vec<AnswerDelta> = self.choices
+            .iter()
+            .filter_map(|choice| choice.delta.content.as_deref())
+            .flat_map(|content| parser.push(content))
+            .collect();
+        if let Some(usage) = &self.usage {
+            deltas.push(AnswerDelta::Usage(usage.clone()));
+        }
+        deltas
+    }
+}
+
+/// Parses answer content streamed in pieces. A `<citation>` or `<usage>` tag split across
+/// pieces is held back until it is complete.
+#[derive(Debug, Default)]
+pub(crate) struct AnswerContentParser {
+    buffer: String,
+}
+
+impl AnswerContentParser {
+    pub(crate) fn push(&mut self, content: &str) -> Vec<AnswerDelta> {
+        self.buffer.push_str(content);
+        let (mut deltas, rest) = split_answer_content(&self.buffer);
+        let held = incomplete_tag_start(rest);
+        if held > 0 {
+            deltas.push(AnswerDelta::Text(rest[..held].to_string()));
+        }
+        self.buffer = rest[held..].to_string();
+        deltas
+    }
+
+    /// Returns what is still held back, as text when a tag was never closed.
+    pub(crate) fn finish(&mut self) -> Vec<AnswerDelta> {
+        parse_answer_content(&std::mem::take(&mut self.buffer))
+    }
+}
+
+/// Splits answer content into text, `<citation>` and `<usage>` parts. Tags whose payload
+/// cannot be parsed are kept as text.
+pub fn parse_answer_content(content: &str) -> Vec<AnswerDelta> {
+    let (mut deltas, rest) = split_answer_content(content);
+    if !rest.is_empty() {
+        deltas.push(AnswerDelta::Text(rest.to_string()));
+    }
+    deltas
+}
+
+/// Parses the complete tags in `content` and the text between them, returning the remainder
+/// after the last complete tag.
+fn split_answer_content(content: &str) -> (Vec<AnswerDelta>, &str) {
+    let mut deltas = vec![];
+    let mut rest = content;
+    while let Some((start, tag)) = next_tag(rest) {
+        let open = format!("<{}>", tag);
+        let close = format!("</{}>", tag);
+        let Some(end) = rest[start..].find(&close).map(|end| start + end) else {
+            break;
+        };
+        if start > 0 {
+            deltas.push(AnswerDelta::Text(rest[..start].to_string()));
+        }
+        let payload = &rest[start + open.len()..end];
+        let parsed = match tag {
+            "citation" => serde_json::from_str(payload).ok().map(AnswerDelta::Citation),
+            _ => serde_json::from_str(payload).ok().map(AnswerDelta::Usage),
+        };
+        deltas.push(
+            parsed.unwrap_or_else(|| AnswerDelta::Text(rest[start..end + close.len()].to_string()))
+        );
+        rest = &rest[end + close.len()..];
+    }
+    (deltas, rest)
+}
+
+/// Where a tag that may continue in the next piece starts: an opening tag without its closing
+/// tag, or a prefix of an opening tag at the very end.
+fn incomplete_tag_start(content: &str) -> usize {
+    if let Some((start, _)) = next_tag(content) {
+        return start;
+    }
+    content
+        .rfind('<')
+        .filter(|start| {
+            let partial = &content[*start..];
+            ["<citation>", "<usage>"].iter().any(|tag| tag.starts_with(partial))
+        })
+        .unwrap_or(content.len())
+}
+
+fn next_tag(content: &str) -> Option<(usize, &'static str)> {
+    ["citation", "usage"]
+        .into_iter()
+        .filter_map(|tag| content.find(&format!("<{}>", tag)).map(|start| (start, tag)))
+        .min_by_key(|(start, _)| *start)
+}
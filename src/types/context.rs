@@ -0,0 +1,284 @@
+use derive_builder::Builder;
+
+use crate::{ brave::BraveClientError, types::Date };
+
+const TIMEZONE_AREAS: [&str; 11] = [
+    "Africa",
+    "America",
+    "Antarctica",
+    "Arctic",
+    "Asia",
+    "Atlantic",
+    "Australia",
+    "Europe",
+    "Indian",
+    "Pacific",
+    "Etc",
+];
+
+/// Location hints sent as `X-Loc-*` headers so local results match the user's whereabouts.
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[builder(setter(into, strip_option), default, build_fn(private, name = "fallible_build"))]
+pub struct LocationHints {
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub timezone: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub state_name: Option<String>,
+    pub country: Option<String>,
+    pub postal_code: Option<String>,
+}
+
+impl LocationHintsBuilder {
+    pub fn build(&self) -> Result<LocationHints, BraveClientError> {
+        let hints = self
+            .fallible_build()
+            .map_err(|err| BraveClientError::InvalidParameter(err.to_string()))?;
+        hints.validate()?;
+        Ok(hints)
+    }
+}
+
+impl LocationHints {
+    pub fn validate(&self) -> Result<(), BraveClientError> {
+        let mut violations = vec![];
+        if let Some(latitude) = self.latitude && !(-90.0..=90.0).contains(&latitude) {
+            violations.push(format!("latitude {} is outside -90..=90", latitude));
+        }
+        if let Some(longitude) = self.longitude && !(-180.0..=180.0).contains(&longitude) {
+            violations.push(format!("longitude {} is outside -180..=180", longitude));
+        }
+        if let Some(timezone) = &self.timezone && !is_iana_timezone(timezone) {
+            violations.push(format!("timezone `{}` is not an IANA timezone name", timezone));
+        }
+        if let Some(state) = &self.state && !is_code(state, 1..=3) {
+            violations.push(format!("state `{}` must be a 1 to 3 character region code", state));
+        }
+        if let Some(country) = &self.country && !is_code(country, 2..=2) {
+            violations.push(format!("country `{}` must be a 2 letter country code", country));
+        }
+        for (name, value) in [
+            ("city", &self.city),
+            ("state_name", &self.state_name),
+            ("postal_code", &self.postal_code),
+        ] {
+            if let Some(value) = value && !is_hint_text(value) {
+                violations.push(format!("{} `{}` must be non-empty printable text", name, value));
+            }
+        }
+        into_result("location hints", violations)
+    }
+
+    fn or(&self, default: &LocationHints) -> LocationHints {
+        LocationHints {
+            latitude: self.latitude.or(default.latitude),
+            longitude: self.longitude.or(default.longitude),
+            timezone: self.timezone.clone().or_else(|| default.timezone.clone()),
+            city: self.city.clone().or_else(|| default.city.clone()),
+            state: self.state.clone().or_else(|| default.state.clone()),
+            state_name: self.state_name.clone().or_else(|| default.state_name.clone()),
+            country: self.country.clone().or_else(|| default.country.clone()),
+            postal_code: self.postal_code.clone().or_else(|| default.postal_code.clone()),
+        }
+    }
+
+    fn headers(&self) -> Vec<(&'static str, String)> {
+        [
+            ("X-Loc-Lat", self.latitude.map(|latitude| latitude.to_string())),
+            ("X-Loc-Long", self.longitude.map(|longitude| longitude.to_string())),
+            ("X-Loc-Timezone", self.timezone.clone()),
+            ("X-Loc-City", self.city.as_deref().map(header_text)),
+            ("X-Loc-State", self.state.clone()),
+            ("X-Loc-State-Name", self.state_name.as_deref().map(header_text)),
+            ("X-Loc-Country", self.country.clone()),
+            ("X-Loc-Postal-Code", self.postal_code.as_deref().map(header_text)),
+        ]
+            .into_iter()
+            .filter_map(|(name, value)| value.map(|value| (name, value)))
+            .collect()
+    }
+}
+
+/// Request headers describing the client and its location. Can be set as a client default or
+/// attached to individual requests, in which case unset fields fall back to the default.
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[builder(setter(into, strip_option), default, build_fn(private, name = "fallible_build"))]
+pub struct SearchContext {
+    pub location: Option<LocationHints>,
+    pub user_agent: Option<String>,
+    pub api_version: Option<Date>,
+    pub no_cache: Option<bool>,
+}
+
+impl SearchContextBuilder {
+    pub fn build(&self) -> Result<SearchContext, BraveClientError> {
+        let context = self
+            .fallible_build()
+            .map_err(|err| BraveClientError::InvalidParameter(err.to_string()))?;
+        context.validate()?;
+        Ok(context)
+    }
+}
+
+impl SearchContext {
+    pub fn validate(&self) -> Result<(), BraveClientError> {
+        if let Some(location) = &self.location {
+            location.validate()?;
+        }
+        let mut violations = vec![];
+        if let Some(user_agent) = &self.user_agent && !is_header_text(user_agent) {
+            violations.push(format!("user_agent `{}` must be printable ASCII", user_agent));
+        }
+        into_result("search context", violations)
+    }
+
+    /// Fills the fields not set on this context from `default`.
+    pub fn or(&self, default: &SearchContext) -> SearchContext {
+        let location = match (&self.location, &default.location) {
+            (Some(location), Some(default)) => Some(location.or(default)),
+            (location, default) => location.clone().or_else(|| default.clone()),
+        };
+        SearchContext {
+            location,
+            user_agent: self.user_agent.clone().or_else(|| default.user_agent.clone()),
+            api_version: self.api_version.or(default.api_version),
+            no_cache: self.no_cache.or(default.no_cache),
+        }
+    }
+
+    pub fn headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = self.location.as_ref().map(LocationHints::headers).unwrap_or_default();
+        if let Some(user_agent) = &self.user_agent {
+            headers.push(("User-Agent", user_agent.clone()));
+        }
+        if let Some(api_version) = &self.api_version {
+            headers.push(("Api-Version", api_version.to_string()));
+        }
+        if self.no_cache == Some(true) {
+            headers.push(("Cache-Control", String::from("no-cache")));
+        }
+        headers
+    }
+}
+
+fn into_result(subject: &str, violations: Vec<String>) -> Result<(), BraveClientError> {
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(BraveClientError::InvalidParameter(format!("{}: {}", subject, violations.join("; "))))
+    }
+}
+
+/// Checks the shape of an IANA timezone name (`UTC` or `Area/Location[/Sublocation]`) against
+/// the known top-level areas.
+fn is_iana_timezone(timezone: &str) -> bool {
+    if timezone == "UTC" {
+        return true;
+    }
+    let mut parts = timezone.split('/');
+    let area = parts.next().unwrap_or_default();
+    let locations: Vec<&str> = parts.collect();
+    TIMEZONE_AREAS.contains(&area) &&
+        (1..=2).contains(&locations.len()) &&
+        locations.iter().all(|location| {
+            !location.is_empty() &&
+                location.chars().all(|c| c.is_ascii_alphanumeric() || "_-+".contains(c))
+        })
+}
+
+fn is_code(value: &str, len: std::ops::RangeInclusive<usize>) -> bool {
+    len.contains(&value.len()) && value.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn is_header_text(value: &str) -> bool {
+    !value.trim().is_empty() && value.chars().all(|c| c.is_ascii() && !c.is_ascii_control())
+}
+
+fn is_hint_text(value: &str) -> bool {
+    !value.trim().is_empty() && !value.chars().any(char::is_control)
+}
+
+/// Header values must be ASCII, so names such as "São Paulo" are sent percent-encoded.
+fn header_text(value: &str) -> String {
+    if value.is_ascii() {
+        value.to_string()
+    } else {
+        urlencoding::encode(value).into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location_hints_reports_every_violation() {
+        let err = LocationHintsBuilder::default()
+            .latitude(91.0)
+            .longitude(-122.4)
+            .timezone("Mars/Olympus_Mons")
+            .country("USA")
+            .build()
+            .unwrap_err();
+
+        let BraveClientError::InvalidParameter(msg) = err else {
+            panic!("Expected BraveClientError::InvalidParameter");
+        };
+        assert!(msg.contains("latitude 91"));
+        assert!(msg.contains("timezone `Mars/Olympus_Mons`"));
+        assert!(msg.contains("country `USA`"));
+        assert!(!msg.contains("longitude"));
+    }
+
+    #[test]
+    fn test_location_hints_percent_encode_non_ascii_names() {
+        let hints = LocationHintsBuilder::default()
+            .city("São Paulo")
+            .state_name("Bayern")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            hints.headers(),
+            vec![
+                ("X-Loc-City", "S%C3%A3o%20Paulo".to_string()),
+                ("X-Loc-State-Name", "Bayern".to_string())
+            ]
+        );
+        assert!(LocationHintsBuilder::default().city("München").build().is_ok());
+        assert!(LocationHintsBuilder::default().city("Mün\nchen").build().is_err());
+    }
+
+    #[test]
+    fn test_search_context_merges_over_default() {
+        let default = SearchContextBuilder::default()
+            .location(
+                LocationHintsBuilder::default()
+                    .timezone("America/Los_Angeles")
+                    .city("San Francisco")
+                    .build()
+                    .unwrap()
+            )
+            .user_agent("brave-rs")
+            .build()
+            .unwrap();
+        let request = SearchContextBuilder::default()
+            .location(LocationHintsBuilder::default().city("Oakland").build().unwrap())
+            .no_cache(true)
+            .build()
+            .unwrap();
+
+        let headers = request.or(&default).headers();
+
+        assert_eq!(
+            headers,
+            vec![
+                ("X-Loc-Timezone", "America/Los_Angeles".to_string()),
+                ("X-Loc-City", "Oakland".to_string()),
+                ("User-Agent", "brave-rs".to_string()),
+                ("Cache-Control", "no-cache".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use std::{ fmt, str::FromStr };
+
+use serde::{ Deserialize, Deserializer, Serialize, Serializer };
+
+use crate::brave::BraveClientError;
+
+/// Declares a catalog of codes supported by the API. Values parse case-insensitively from either
+/// the code or the name, and serialize to the exact code the API expects.
+macro_rules! catalog {
+    ($name:ident, $param:literal, { $($variant:ident => ($code:literal, $label:literal)),+ $(,)? }) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($variant),+
+        }
+
+        impl $name {
+            pub const ALL: &'static [$name] = &[$($name::$variant),+];
+
+            pub fn code(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $code),+
+                }
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $label),+
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.code())
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = BraveClientError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let value = s.trim();
+                $name::ALL
+                    .iter()
+                    .find(|entry| {
+                        entry.code().eq_ignore_ascii_case(value) ||
+                            entry.name().eq_ignore_ascii_case(value)
+                    })
+                    .copied()
+                    .ok_or_else(|| {
+                        let codes: Vec<_> = $name::ALL.iter().map(|entry| entry.code()).collect();
+                        BraveClientError::InvalidParameter(
+                            format!(
+                                "{} `{}`: unsupported, expected one of {}",
+                                $param,
+                                s,
+                                codes.join(", ")
+                            )
+                        )
+                    })
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.code())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                value.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+catalog!(Country, "country", {
+    All => ("ALL", "All Regions"),
+    Ar => ("AR", "Argentina"),
+    Au => ("AU", "Australia"),
+    At => ("AT", "Austria"),
+    Be => ("BE", "Belgium"),
+    Br => ("BR", "Brazil"),
+    Ca => ("CA", "Canada"),
+    Cl => ("CL", "Chile"),
+    Dk => ("DK", "Denmark"),
+    Fi => ("FI", "Finland"),
+    Fr => ("FR", "France"),
+    De => ("DE", "Germany"),
+    Hk => ("HK", "Hong Kong"),
+    In => ("IN", "India"),
+    Id => ("ID", "Indonesia"),
+    It => ("IT", "Italy"),
+    Jp => ("JP", "Japan"),
+    Kr => ("KR", "Korea"),
+    My => ("MY", "Malaysia"),
+    Mx => ("MX", "Mexico"),
+    Nl => ("NL", "Netherlands"),
+    Nz => ("NZ", "New Zealand"),
+    No => ("NO", "Norway"),
+    Cn => ("CN", "People's Republic of China"),
+    Pl => ("PL", "Poland"),
+    Pt => ("PT", "Portugal"),
+    Ph => ("PH", "Republic of the Philippines"),
+    Ru => ("RU", "Russia"),
+    Sa => ("SA", "Saudi Arabia"),
+    Za => ("ZA", "South Africa"),
+    Es => ("ES", "Spain"),
+    Se => ("SE", "Sweden"),
+    Ch => ("CH", "Switzerland"),
+    Tw => ("TW", "Taiwan"),
+    Tr => ("TR", "Turkey"),
+    Gb => ("GB", "United Kingdom"),
+    Us => ("US", "United States"),
+});
+
+catalog!(SearchLang, "search_lang", {
+    Ar => ("ar", "Arabic"),
+    Eu => ("eu", "Basque"),
+    Bn => ("bn", "Bengali"),
+    Bg => ("bg", "Bulgarian"),
+    Ca => ("ca", "Catalan"),
+    ZhHans => ("zh-hans", "Chinese Simplified"),
+    ZhHant => ("zh-hant", "Chinese Traditional"),
+    Hr => ("hr", "Croatian"),
+    Cs => ("cs", "Czech"),
+    Da => ("da", "Danish"),
+    Nl => ("nl", "Dutch"),
+    En => ("en", "English"),
+    EnGb => ("en-gb", "English United Kingdom"),
+    Et => ("et", "Estonian"),
+    Fi => ("fi", "Finnish"),
+    Fr => ("fr", "French"),
+    Gl => ("gl", "Galician"),
+    De => ("de", "German"),
+    Gu => ("gu", "Gujarati"),
+    He => ("he", "Hebrew"),
+    Hi => ("hi", "Hindi"),
+    Hu => ("hu", "Hungarian"),
+    Is => ("is", "Icelandic"),
+    It => ("it", "Italian"),
+    Jp => ("jp", "Japanese"),
+    Kn => ("kn", "Kannada"),
+    Ko => ("ko", "Korean"),
+    Lv => ("lv", "Latvian"),
+    Lt => ("lt", "Lithuanian"),
+    Ms => ("ms", "Malay"),
+    Ml => ("ml", "Malayalam"),
+    Mr => ("mr", "Marathi"),
+    Nb => ("nb", "Norwegian Bokmål"),
+    Pl => ("pl", "Polish"),
+    PtBr => ("pt-br", "Portuguese Brazil"),
+    PtPt => ("pt-pt", "Portuguese Portugal"),
+    Pa => ("pa", "Punjabi"),
+    Ro => ("ro", "Romanian"),
+    Ru => ("ru", "Russian"),
+    Sr => ("sr", "Serbian Cyrillic"),
+    Sk => ("sk", "Slovak"),
+    Sl => ("sl", "Slovenian"),
+    Es => ("es", "Spanish"),
+    Sv => ("sv", "Swedish"),
+    Ta => ("ta", "Tamil"),
+    Te => ("te", "Telugu"),
+    Th => ("th", "Thai"),
+    Tr => ("tr", "Turkish"),
+    Uk => ("uk", "Ukrainian"),
+    Vi => ("vi", "Vietnamese"),
+});
+
+catalog!(UiLang, "ui_lang", {
+    EsAr => ("es-AR", "Argentina (Spanish)"),
+    EnAu => ("en-AU", "Australia (English)"),
+    DeAt => ("de-AT", "Austria (German)"),
+    NlBe => ("nl-BE", "Belgium (Dutch)"),
+    FrBe => ("fr-BE", "Belgium (French)"),
+    PtBr => ("pt-BR", "Brazil (Portuguese)"),
+    EnCa => ("en-CA", "Canada (English)"),
+    FrCa => ("fr-CA", "Canada (French)"),
+    EsCl => ("es-CL", "Chile (Spanish)"),
+    DaDk => ("da-DK", "Denmark (Danish)"),
+    FiFi => ("fi-FI", "Finland (Finnish)"),
+    FrFr => ("fr-FR", "France (French)"),
+    DeDe => ("de-DE", "Germany (German)"),
+    ZhHk => ("zh-HK", "Hong Kong (Traditional Chinese)"),
+    EnIn => ("en-IN", "India (English)"),
+    EnId => ("en-ID", "Indonesia (English)"),
+    ItIt => ("it-IT", "Italy (Italian)"),
+    JaJp => ("ja-JP", "Japan (Japanese)"),
+    KoKr => ("ko-KR", "Korea (Korean)"),
+    EnMy => ("en-MY", "Malaysia (English)"),
+    EsMx => ("es-MX", "Mexico (Spanish)"),
+    NlNl => ("nl-NL", "Netherlands (Dutch)"),
+    EnNz => ("en-NZ", "New Zealand (English)"),
+    NoNo => ("no-NO", "Norway (Norwegian)"),
+    ZhCn => ("zh-CN", "People's Republic of China (Chinese)"),
+    PlPl => ("pl-PL", "Poland (Polish)"),
+    EnPh => ("en-PH", "Republic of the Philippines (English)"),
+    RuRu => ("ru-RU", "Russia (Russian)"),
+    EnZa => ("en-ZA", "South Africa (English)"),
+    EsEs => ("es-ES", "Spain (Spanish)"),
+    SvSe => ("sv-SE", "Sweden (Swedish)"),
+    FrCh => ("fr-CH", "Switzerland (French)"),
+    DeCh => ("de-CH", "Switzerland (German)"),
+    ZhTw => ("zh-TW", "Taiwan (Traditional Chinese)"),
+    TrTr => ("tr-TR", "Turkey (Turkish)"),
+    EnGb => ("en-GB", "United Kingdom (English)"),
+    EnUs => ("en-US", "United States (English)"),
+    EsUs => ("es-US", "United States (Spanish)"),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalogs_parse_codes_and_names_case_insensitively() {
+        assert_eq!("us".parse::<Country>().unwrap(), Country::Us);
+        assert_eq!("united kingdom".parse::<Country>().unwrap(), Country::Gb);
+        assert_eq!("EN".parse::<SearchLang>().unwrap(), SearchLang::En);
+        assert_eq!("Chinese Simplified".parse::<SearchLang>().unwrap(), SearchLang::ZhHans);
+        assert_eq!("de-de".parse::<UiLang>().unwrap(), UiLang::DeDe);
+    }
+
+    #[test]
+    fn test_catalogs_serialize_to_api_codes() {
+        assert_eq!(Country::Us.to_string(), "US");
+        assert_eq!(SearchLang::PtBr.to_string(), "pt-br");
+        assert_eq!(UiLang::EnGb.to_string(), "en-GB");
+        assert_eq!(serde_json::to_string(&Country::Gb).unwrap(), "\"GB\"");
+        assert_eq!(serde_json::from_str::<Country>("\"de\"").unwrap(), Country::De);
+    }
+
+    #[test]
+    fn test_catalogs_reject_unsupported_values() {
+        let err = "usa".parse::<Country>().unwrap_err();
+        assert!(
+            matches!(&err, BraveClientError::InvalidParameter(msg) if msg.starts_with("country `usa`"))
+        );
+        assert!("english".parse::<UiLang>().is_err());
+        assert!(serde_json::from_str::<Country>("\"xx\"").is_err());
+    }
+}
@@ -1,6 +1,42 @@
 pub mod response;
 pub mod query_params;
+pub mod search_options;
+pub mod locale;
+pub mod context;
+pub mod search_query;
+pub mod summarizer;
+pub mod rich;
+pub mod answers;
+pub mod rate_limit;
 
 // Re-export the main types users will need
-pub use response::WebSearchApiResponse;
-pub use query_params::WebSearchQueryParams;
+pub use response::{
+    ImageSearchApiResponse,
+    LocalDescriptionsSearchApiResponse,
+    LocalPoiSearchApiResponse,
+    NewsSearchApiResponse,
+    Section,
+    SpellcheckApiResponse,
+    SuggestApiResponse,
+    VideoSearchApiResponse,
+    WebSearchApiResponse,
+};
+pub use context::{ LocationHints, SearchContext };
+pub use locale::{ Country, SearchLang, UiLang };
+pub use search_query::{ Operator, QueryNode, SearchQuery };
+pub use search_options::{ Date, Freshness, ResultFilter, ResultSection, SafeSearch, Units };
+pub use summarizer::Summary;
+pub use rich::{ RichResult, RichSearchApiResponse };
+pub use answers::{ AnswerDelta, AnswerRequest, AnswerResponse };
+pub use rate_limit::{ RateLimitInfo, RateLimitWindow };
+pub use query_params::{
+    ExtraParams,
+    QueryDefaults,
+    ImageSearchQueryParams,
+    NewsSearchQueryParams,
+    PageOptions,
+    SpellcheckQueryParams,
+    SuggestQueryParams,
+    VideoSearchQueryParams,
+    WebSearchQueryParams,
+};
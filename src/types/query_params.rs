@@ -1,14 +1,393 @@
+use std::time::Duration;
+
 use derive_builder::Builder;
+use serde::{ ser::SerializeMap, Serialize, Serializer };
+
+use crate::brave::BraveClientError;
+use crate::brave::query_encoder::flag;
+use crate::types::{
+    Country,
+    Freshness,
+    ResultFilter,
+    SafeSearch,
+    SearchLang,
+    UiLang,
+    Units,
+    VideoSearchApiResponse,
+    WebSearchApiResponse,
+};
 
-#[derive(Debug, Default, Builder)]
+#[derive(Debug, Default, Clone, Serialize, Builder)]
 #[builder(setter(into, strip_option), default)]
 pub struct WebSearchQueryParams {
     pub q: Option<String>,
     pub count: Option<u32>,
     pub offset: Option<u32>,
-    pub search_lang: Option<String>,
-    pub safesearch: Option<String>,
-    pub freshness: Option<String>,
-    pub country: Option<String>,
-    pub source: Option<String>,
+    pub search_lang: Option<SearchLang>,
+    pub ui_lang: Option<UiLang>,
+    pub safesearch: Option<SafeSearch>,
+    pub freshness: Option<Freshness>,
+    pub country: Option<Country>,
+    #[serde(serialize_with = "flag")]
+    pub text_decorations: Option<bool>,
+    #[serde(serialize_with = "flag")]
+    pub spellcheck: Option<bool>,
+    pub result_filter: Option<ResultFilter>,
+    pub units: Option<Units>,
+    pub extra_snippets: Option<bool>,
+    #[serde(serialize_with = "flag")]
+    pub summary: Option<bool>,
+    #[serde(serialize_with = "flag")]
+    pub enable_rich_callback: Option<bool>,
+    pub goggles_id: Option<String>,
+    pub goggles: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub extra_params: ExtraParams,
+}
+
+impl WebSearchQueryParams {
+    pub const MAX_QUERY_CHARS: usize = 400;
+    pub const MAX_QUERY_WORDS: usize = 50;
+    pub const MAX_COUNT: u32 = 20;
+    pub const MAX_OFFSET: u32 = 9;
+
+    /// Checks the params against the API's documented limits, reporting every violation.
+    pub fn validate(&self) -> Result<(), BraveClientError> {
+        let mut violations = vec![];
+        match self.q.as_deref().map(str::trim) {
+            None | Some("") => violations.push(String::from("q must not be empty")),
+            Some(q) => {
+                let chars = q.chars().count();
+                if chars > Self::MAX_QUERY_CHARS {
+                    violations.push(
+                        format!("q is {} characters, maximum is {}", chars, Self::MAX_QUERY_CHARS)
+                    );
+                }
+                let words = q.split_whitespace().count();
+                if words > Self::MAX_QUERY_WORDS {
+                    violations.push(
+                        format!("q is {} words, maximum is {}", words, Self::MAX_QUERY_WORDS)
+                    );
+                }
+            }
+        }
+        if let Some(count) = self.count && count > Self::MAX_COUNT {
+            violations.push(format!("count is {}, maximum is {}", count, Self::MAX_COUNT));
+        }
+        if let Some(offset) = self.offset && offset > Self::MAX_OFFSET {
+            violations.push(format!("offset is {}, maximum is {}", offset, Self::MAX_OFFSET));
+        }
+        violations.extend(freshness_violation(self.freshness));
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(BraveClientError::InvalidQuery(violations))
+        }
+    }
+
+    /// Returns the params for the following page, or `None` once the API reports no more
+    /// results or the maximum offset has been reached.
+    pub fn next_page(&self, response: &WebSearchApiResponse) -> Option<Self> {
+        if !response.query.more_results_available {
+            return None;
+        }
+        let offset = self.offset.unwrap_or(0) + 1;
+        if offset > Self::MAX_OFFSET {
+            return None;
+        }
+        Some(Self { offset: Some(offset), ..self.clone() })
+    }
+}
+
+/// Limits for [`BraveClient::web_search_pages`].
+///
+/// [`BraveClient::web_search_pages`]: crate::BraveClient::web_search_pages
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PageOptions {
+    /// Stop after this many pages, in addition to the API's own limits.
+    pub max_pages: Option<usize>,
+    /// Wait between pages, e.g. to stay within the plan's per-second limit.
+    pub delay: Option<Duration>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Builder)]
+#[builder(setter(into, strip_option), default)]
+pub struct ImageSearchQueryParams {
+    pub q: Option<String>,
+    pub country: Option<Country>,
+    pub search_lang: Option<SearchLang>,
+    pub count: Option<u32>,
+    /// Image search supports `off` and `strict` only.
+    pub safesearch: Option<SafeSearch>,
+    #[serde(serialize_with = "flag")]
+    pub spellcheck: Option<bool>,
+    #[serde(flatten)]
+    pub extra_params: ExtraParams,
+}
+
+impl ImageSearchQueryParams {
+    pub fn validate(&self) -> Result<(), BraveClientError> {
+        if self.safesearch == Some(SafeSearch::Moderate) {
+            return Err(
+                BraveClientError::InvalidQuery(
+                    vec![String::from("safesearch moderate is not supported, expected off or strict")]
+                )
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Builder)]
+#[builder(setter(into, strip_option), default)]
+pub struct NewsSearchQueryParams {
+    pub q: Option<String>,
+    pub country: Option<Country>,
+    pub search_lang: Option<SearchLang>,
+    pub ui_lang: Option<UiLang>,
+    pub count: Option<u32>,
+    pub offset: Option<u32>,
+    pub safesearch: Option<SafeSearch>,
+    pub freshness: Option<Freshness>,
+    #[serde(serialize_with = "flag")]
+    pub spellcheck: Option<bool>,
+    pub extra_snippets: Option<bool>,
+    pub goggles: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub extra_params: ExtraParams,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Builder)]
+#[builder(setter(into, strip_option), default)]
+pub struct VideoSearchQueryParams {
+    pub q: Option<String>,
+    pub country: Option<Country>,
+    pub search_lang: Option<SearchLang>,
+    pub ui_lang: Option<UiLang>,
+    pub count: Option<u32>,
+    pub offset: Option<u32>,
+    pub safesearch: Option<SafeSearch>,
+    pub freshness: Option<Freshness>,
+    #[serde(serialize_with = "flag")]
+    pub spellcheck: Option<bool>,
+    #[serde(flatten)]
+    pub extra_params: ExtraParams,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Builder)]
+#[builder(setter(into, strip_option), default)]
+pub struct SuggestQueryParams {
+    pub q: Option<String>,
+    pub country: Option<Country>,
+    pub lang: Option<String>,
+    pub count: Option<u32>,
+    pub rich: Option<bool>,
+    #[serde(flatten)]
+    pub extra_params: ExtraParams,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Builder)]
+#[builder(setter(into, strip_option), default)]
+pub struct SpellcheckQueryParams {
+    pub q: Option<String>,
+    pub lang: Option<String>,
+    pub country: Option<Country>,
+    #[serde(flatten)]
+    pub extra_params: ExtraParams,
+}
+
+impl NewsSearchQueryParams {
+    pub fn validate(&self) -> Result<(), BraveClientError> {
+        match freshness_violation(self.freshness) {
+            Some(violation) => Err(BraveClientError::InvalidQuery(vec![violation])),
+            None => Ok(()),
+        }
+    }
+}
+
+impl VideoSearchQueryParams {
+    pub const MAX_OFFSET: u32 = 9;
+
+    pub fn validate(&self) -> Result<(), BraveClientError> {
+        match freshness_violation(self.freshness) {
+            Some(violation) => Err(BraveClientError::InvalidQuery(vec![violation])),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the params for the following page, or `None` once the API reports no more
+    /// results or the maximum offset has been reached.
+    pub fn next_page(&self, response: &VideoSearchApiResponse) -> Option<Self> {
+        if !response.query.more_results_available.unwrap_or(false) {
+            return None;
+        }
+        let offset = self.offset.unwrap_or(0) + 1;
+        if offset > Self::MAX_OFFSET {
+            return None;
+        }
+        Some(Self { offset: Some(offset), ..self.clone() })
+    }
+}
+
+/// `Freshness::Range` can be built without `Freshness::range`, so ranges are checked again
+/// before sending.
+fn freshness_violation(freshness: Option<Freshness>) -> Option<String> {
+    match freshness {
+        Some(Freshness::Range(start, end)) if end < start => {
+            Some(format!("freshness range {}to{} ends before it starts", start, end))
+        }
+        _ => None,
+    }
+}
+
+/// Client-wide defaults for params a request leaves unset.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct QueryDefaults {
+    pub country: Option<Country>,
+    pub search_lang: Option<SearchLang>,
+    pub safesearch: Option<SafeSearch>,
+}
+
+impl QueryDefaults {
+    pub fn is_empty(&self) -> bool {
+        self == &QueryDefaults::default()
+    }
+}
+
+/// Fills unset params from the client's [`QueryDefaults`].
+pub(crate) trait ApplyDefaults {
+    fn apply_defaults(&mut self, defaults: &QueryDefaults);
+}
+
+macro_rules! impl_apply_defaults {
+    ($($params:ty),+) => {
+        $(
+            impl ApplyDefaults for $params {
+                fn apply_defaults(&mut self, defaults: &QueryDefaults) {
+                    self.country = self.country.or(defaults.country);
+                    self.search_lang = self.search_lang.or(defaults.search_lang);
+                    self.safesearch = self.safesearch.or(defaults.safesearch);
+                }
+            }
+        )+
+    };
+}
+
+impl_apply_defaults!(WebSearchQueryParams, NewsSearchQueryParams, VideoSearchQueryParams);
+
+impl ApplyDefaults for ImageSearchQueryParams {
+    fn apply_defaults(&mut self, defaults: &QueryDefaults) {
+        self.country = self.country.or(defaults.country);
+        self.search_lang = self.search_lang.or(defaults.search_lang);
+        // Image search only supports `off` and `strict`, so `moderate` keeps the API default.
+        if self.safesearch.is_none() && defaults.safesearch != Some(SafeSearch::Moderate) {
+            self.safesearch = defaults.safesearch;
+        }
+    }
+}
+
+impl ApplyDefaults for SuggestQueryParams {
+    fn apply_defaults(&mut self, defaults: &QueryDefaults) {
+        self.country = self.country.or(defaults.country);
+        if self.lang.is_none() {
+            self.lang = defaults.search_lang.map(|lang| lang.code().to_string());
+        }
+    }
+}
+
+impl ApplyDefaults for SpellcheckQueryParams {
+    fn apply_defaults(&mut self, defaults: &QueryDefaults) {
+        self.country = self.country.or(defaults.country);
+        if self.lang.is_none() {
+            self.lang = defaults.search_lang.map(|lang| lang.code().to_string());
+        }
+    }
+}
+
+/// Additional `key=value` pairs appended to the query string as-is, for API params this crate
+/// does not model yet.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExtraParams(pub Vec<(String, String)>);
+
+impl<K: Into<String>, V: Into<String>> From<Vec<(K, V)>> for ExtraParams {
+    fn from(params: Vec<(K, V)>) -> Self {
+        ExtraParams(
+            params
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect()
+        )
+    }
+}
+
+impl Serialize for ExtraParams {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Date;
+
+    #[test]
+    fn test_news_and_video_params_reject_reversed_freshness_ranges() {
+        let reversed = Freshness::Range(Date::new(2025, 2, 1).unwrap(), Date::new(2025, 1, 1).unwrap());
+        let news = NewsSearchQueryParams { freshness: Some(reversed), ..Default::default() };
+        let video = VideoSearchQueryParams { freshness: Some(reversed), ..Default::default() };
+
+        assert!(matches!(news.validate(), Err(BraveClientError::InvalidQuery(_))));
+        assert!(matches!(video.validate(), Err(BraveClientError::InvalidQuery(_))));
+        assert!(NewsSearchQueryParams::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_web_search_query_params_validate_reports_every_violation() {
+        let params = WebSearchQueryParamsBuilder::default()
+            .q("word ".repeat(81))
+            .count(21u32)
+            .offset(10u32)
+            .freshness(
+                Freshness::Range(Date::new(2025, 2, 1).unwrap(), Date::new(2025, 1, 1).unwrap())
+            )
+            .build()
+            .unwrap();
+
+        let Err(BraveClientError::InvalidQuery(violations)) = params.validate() else {
+            panic!("Expected BraveClientError::InvalidQuery");
+        };
+        assert_eq!(
+            violations,
+            vec![
+                "q is 404 characters, maximum is 400",
+                "q is 81 words, maximum is 50",
+                "count is 21, maximum is 20",
+                "offset is 10, maximum is 9",
+                "freshness range 2025-02-01to2025-01-01 ends before it starts"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_web_search_query_params_validate_accepts_limits() {
+        let params = WebSearchQueryParamsBuilder::default()
+            .q("a".repeat(400))
+            .count(20u32)
+            .offset(9u32)
+            .build()
+            .unwrap();
+
+        assert!(params.validate().is_ok());
+        assert!(
+            matches!(
+                WebSearchQueryParams::default().validate(),
+                Err(BraveClientError::InvalidQuery(violations)) if violations == ["q must not be empty"]
+            )
+        );
+    }
 }
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+
+/// Rate limit state reported by the `X-RateLimit-*` headers of a response. Brave reports one
+/// window per limit of the plan, e.g. per second and per month.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    pub windows: Vec<RateLimitWindow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitWindow {
+    /// Requests allowed per window.
+    pub limit: u64,
+    /// Window length from `X-RateLimit-Policy`, when reported.
+    pub window: Option<Duration>,
+    pub remaining: u64,
+    /// Time until the window resets.
+    pub reset: Duration,
+}
+
+impl RateLimitInfo {
+    /// Parses the `X-RateLimit-*` headers. Returns `None` when none are present.
+    pub fn from_headers(headers: &HeaderMap) -> Option<RateLimitInfo> {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.split(',').map(str::trim).collect::<Vec<_>>())
+                .unwrap_or_default()
+        };
+        let limits = header("X-RateLimit-Limit");
+        let policies = header("X-RateLimit-Policy");
+        let remaining = header("X-RateLimit-Remaining");
+        let resets = header("X-RateLimit-Reset");
+        let len = [&limits, &policies, &remaining, &resets].iter().map(|values| values.len()).max()?;
+        if len == 0 {
+            return None;
+        }
+        let number = |values: &[&str], i: usize| {
+            values.get(i).and_then(|value| value.parse::<u64>().ok()).unwrap_or_default()
+        };
+        let windows = (0..len)
+            .map(|i| RateLimitWindow {
+                limit: number(&limits, i),
+                window: policies.get(i).and_then(|policy| policy_window(policy)),
+                remaining: number(&remaining, i),
+                reset: Duration::from_secs(number(&resets, i)),
+            })
+            .collect();
+        Some(RateLimitInfo { windows })
+    }
+
+    /// The shortest window, usually the per-second limit.
+    pub fn per_second(&self) -> Option<&RateLimitWindow> {
+        self.windows.iter().min_by_key(|window| window.window.unwrap_or(window.reset))
+    }
+
+    /// The longest window, usually the monthly quota.
+    pub fn per_month(&self) -> Option<&RateLimitWindow> {
+        self.windows.iter().max_by_key(|window| window.window.unwrap_or(window.reset))
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.windows.iter().any(|window| window.remaining == 0)
+    }
+
+    /// How long until requests are accepted again: the latest reset among the used-up windows,
+    /// or the earliest reset when none is reported as used up.
+    pub fn reset_delay(&self) -> Option<Duration> {
+        let exhausted = self.windows
+            .iter()
+            .filter(|window| window.remaining == 0)
+            .map(|window| window.reset)
+            .max();
+        exhausted.or_else(|| self.windows.iter().map(|window| window.reset).min())
+    }
+}
+
+/// Reads the window length from a policy such as `15000;w=2592000`.
+fn policy_window(policy: &str) -> Option<Duration> {
+    policy
+        .split(';')
+        .filter_map(|part| part.trim().strip_prefix("w="))
+        .find_map(|seconds| seconds.parse().ok())
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_rate_limit_info_from_headers() {
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            ("X-RateLimit-Limit", "1, 15000"),
+            ("X-RateLimit-Policy", "1;w=1, 15000;w=2592000"),
+            ("X-RateLimit-Remaining", "0, 14000"),
+            ("X-RateLimit-Reset", "1, 1419704"),
+        ] {
+            headers.insert(name, HeaderValue::from_static(value));
+        }
+
+        let info = RateLimitInfo::from_headers(&headers).unwrap();
+
+        assert_eq!(
+            info.per_second(),
+            Some(
+                &(RateLimitWindow {
+                    limit: 1,
+                    window: Some(Duration::from_secs(1)),
+                    remaining: 0,
+                    reset: Duration::from_secs(1),
+                })
+            )
+        );
+        assert_eq!(info.per_month().map(|window| window.remaining), Some(14000));
+        assert!(info.is_exhausted());
+        assert_eq!(info.reset_delay(), Some(Duration::from_secs(1)));
+        assert_eq!(RateLimitInfo::from_headers(&HeaderMap::new()), None);
+    }
+}
@@ -1,5 +1,10 @@
 use serde::{ Deserialize, Serialize };
 
+use crate::{
+    brave::CacheStatus,
+    types::{ rich::RichCallbackInfo, Country, ResultFilter, ResultSection },
+};
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WebSearchApiResponse {
     #[serde(rename = "type")]
@@ -13,6 +18,101 @@ pub struct WebSearchApiResponse {
     pub web: Option<SearchResults>,
     pub videos: Option<VideoResults>,
     pub mixed: Option<MixedResponse>,
+    pub summarizer: Option<Summarizer>,
+    pub rich: Option<RichCallbackInfo>,
+    #[serde(skip)]
+    pub cache: CacheStatus,
+    /// The `result_filter` the request was sent with, set by the client.
+    #[serde(skip)]
+    pub result_filter: Option<ResultFilter>,
+}
+
+/// A response section, distinguishing sections left out by `result_filter` from sections that
+/// were requested but came back without results.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Section<T> {
+    NotRequested,
+    Empty,
+    Results(T),
+}
+
+impl<T> Section<T> {
+    pub fn results(self) -> Option<T> {
+        match self {
+            Section::Results(results) => Some(results),
+            _ => None,
+        }
+    }
+
+    pub fn is_requested(&self) -> bool {
+        !matches!(self, Section::NotRequested)
+    }
+}
+
+impl WebSearchApiResponse {
+    pub fn web_section(&self) -> Section<&SearchResults> {
+        self.section(ResultSection::Web, self.web.as_ref())
+    }
+
+    pub fn news_section(&self) -> Section<&NewsResults> {
+        self.section(ResultSection::News, self.news.as_ref())
+    }
+
+    pub fn videos_section(&self) -> Section<&VideoResults> {
+        self.section(ResultSection::Videos, self.videos.as_ref())
+    }
+
+    pub fn discussions_section(&self) -> Section<&SearchResults> {
+        self.section(ResultSection::Discussions, self.discussions.as_ref())
+    }
+
+    pub fn faq_section(&self) -> Section<&FaqResults> {
+        self.section(ResultSection::Faq, self.faq.as_ref())
+    }
+
+    pub fn infobox_section(&self) -> Section<&Infobox> {
+        self.section(ResultSection::Infobox, self.infobox.as_ref())
+    }
+
+    pub fn locations_section(&self) -> Section<&LocationResults> {
+        self.section(ResultSection::Locations, self.locations.as_ref())
+    }
+
+    pub fn summarizer_section(&self) -> Section<&Summarizer> {
+        self.section(ResultSection::Summarizer, self.summarizer.as_ref())
+    }
+
+    fn section<'a, T>(&self, section: ResultSection, results: Option<&'a T>) -> Section<&'a T> {
+        match (results, self.result_filter) {
+            (Some(results), _) => Section::Results(results),
+            (None, Some(filter)) if !filter.is_empty() && !filter.contains(section) => {
+                Section::NotRequested
+            }
+            (None, _) => Section::Empty,
+        }
+    }
+
+    /// Whether any result section was re-ranked by the goggles sent with the request.
+    pub fn mutated_by_goggles(&self) -> bool {
+        [
+            self.web.as_ref().map(|web| web.mutated_by_goggles),
+            self.discussions.as_ref().map(|discussions| discussions.mutated_by_goggles),
+            self.faq.as_ref().map(|faq| faq.mutated_by_goggles),
+            self.locations.as_ref().map(|locations| locations.mutated_by_goggles),
+            self.news.as_ref().map(|news| news.mutated_by_goggles),
+            self.videos.as_ref().map(|videos| videos.mutated_by_goggles),
+        ]
+            .into_iter()
+            .flatten()
+            .any(|mutated| mutated)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Summarizer {
+    #[serde(rename = "type")]
+    pub result_type: String,
+    pub key: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -20,6 +120,8 @@ pub struct SearchResults {
     #[serde(rename = "type")]
     pub result_type: String,
     pub results: Vec<SearchResult>,
+    #[serde(default)]
+    pub mutated_by_goggles: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -57,6 +159,8 @@ pub struct FaqResults {
     #[serde(rename = "type")]
     pub result_type: String,
     pub results: Vec<FaqResult>,
+    #[serde(default)]
+    pub mutated_by_goggles: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -86,6 +190,8 @@ pub struct Thumbnail {
     pub src: String,
     pub original: Option<String>,
     pub logo: Option<bool>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -99,12 +205,15 @@ pub struct LocationResults {
     #[serde(rename = "type")]
     pub result_type: String,
     pub results: Vec<LocationResult>,
+    #[serde(default)]
+    pub mutated_by_goggles: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LocationResult {
     #[serde(rename = "type")]
     pub result_type: String,
+    pub id: Option<String>,
     pub url: String,
     pub title: String,
     pub description: String,
@@ -112,6 +221,52 @@ pub struct LocationResult {
     pub postal_address: Option<PostalAddress>,
     pub contact: Option<Contact>,
     pub rating: Option<Rating>,
+    pub thumbnail: Option<Thumbnail>,
+    pub opening_hours: Option<OpeningHours>,
+    pub price_range: Option<String>,
+    pub pictures: Option<PictureResults>,
+    pub reviews: Option<Reviews>,
+    pub categories: Option<Vec<String>>,
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpeningHours {
+    pub current_day: Option<Vec<DayOpeningHours>>,
+    pub days: Option<Vec<Vec<DayOpeningHours>>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DayOpeningHours {
+    pub abbr_name: String,
+    pub full_name: String,
+    pub opens: String,
+    pub closes: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PictureResults {
+    #[serde(rename = "viewMoreUrl")]
+    pub view_more_url: Option<String>,
+    pub results: Vec<Thumbnail>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Reviews {
+    pub results: Vec<Review>,
+    #[serde(rename = "viewMoreUrl")]
+    pub view_more_url: Option<String>,
+    pub reviews_in_foreign_language: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Review {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub date: Option<String>,
+    pub rating: Option<Rating>,
+    pub author: Option<Profile>,
+    pub review_url: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -168,6 +323,8 @@ pub struct NewsResults {
     #[serde(rename = "type")]
     pub result_type: String,
     pub results: Vec<NewsResult>,
+    #[serde(default)]
+    pub mutated_by_goggles: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -179,6 +336,9 @@ pub struct NewsResult {
     pub page_age: Option<String>,
     pub breaking: Option<bool>,
     pub profile: Option<Profile>,
+    pub meta_url: Option<MetaUrl>,
+    pub thumbnail: Option<Thumbnail>,
+    pub extra_snippets: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -188,6 +348,7 @@ pub struct Query {
     pub is_navigational: bool,
     pub is_news_breaking: bool,
     pub spellcheck_off: bool,
+    /// The country code as reported by the API. See [`Query::country`] for the typed value.
     pub country: String,
     pub bad_results: bool,
     pub should_fallback: bool,
@@ -198,11 +359,19 @@ pub struct Query {
     pub state: String,
 }
 
+impl Query {
+    /// The reported country, or `None` when the code is empty or not in the [`Country`] catalog.
+    pub fn country(&self) -> Option<Country> {
+        self.country.parse().ok()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct VideoResults {
     #[serde(rename = "type")]
     pub result_type: String,
     pub results: Option<Vec<VideoResult>>,
+    #[serde(default)]
     pub mutated_by_goggles: bool,
 }
 
@@ -241,3 +410,142 @@ pub struct VideoData {
     pub author: Option<Profile>,
     pub requires_subscription: Option<bool>,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerticalQuery {
+    pub original: String,
+    pub altered: Option<String>,
+    pub cleaned: Option<String>,
+    pub spellcheck_off: Option<bool>,
+    pub show_strict_warning: Option<bool>,
+    pub more_results_available: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImageSearchApiResponse {
+    #[serde(rename = "type")]
+    pub result_type: String,
+    pub query: VerticalQuery,
+    pub results: Vec<ImageResult>,
+    pub extra: Option<ImageExtra>,
+    #[serde(skip)]
+    pub cache: CacheStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImageResult {
+    #[serde(rename = "type")]
+    pub result_type: String,
+    pub title: String,
+    pub url: String,
+    pub source: String,
+    pub page_fetched: Option<String>,
+    pub thumbnail: Thumbnail,
+    pub properties: ImageProperties,
+    pub meta_url: Option<MetaUrl>,
+    pub confidence: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImageProperties {
+    pub url: String,
+    pub placeholder: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImageExtra {
+    pub might_be_offensive: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VideoSearchApiResponse {
+    #[serde(rename = "type")]
+    pub result_type: String,
+    pub query: VerticalQuery,
+    pub results: Vec<VideoResult>,
+    pub extra: Option<VideoExtra>,
+    #[serde(skip)]
+    pub cache: CacheStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VideoExtra {
+    pub might_be_offensive: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewsSearchApiResponse {
+    #[serde(rename = "type")]
+    pub result_type: String,
+    pub query: VerticalQuery,
+    pub results: Vec<NewsResult>,
+    #[serde(skip)]
+    pub cache: CacheStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SuggestApiResponse {
+    #[serde(rename = "type")]
+    pub result_type: String,
+    pub query: SuggestQuery,
+    pub results: Vec<Suggestion>,
+    #[serde(skip)]
+    pub cache: CacheStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SuggestQuery {
+    pub original: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Suggestion {
+    pub query: String,
+    pub is_entity: Option<bool>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub img: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpellcheckApiResponse {
+    #[serde(rename = "type")]
+    pub result_type: String,
+    pub query: VerticalQuery,
+    pub results: Vec<SpellcheckResult>,
+    #[serde(skip)]
+    pub cache: CacheStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpellcheckResult {
+    pub query: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LocalPoiSearchApiResponse {
+    #[serde(rename = "type")]
+    pub result_type: String,
+    pub results: Vec<LocationResult>,
+    #[serde(skip)]
+    pub cache: CacheStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LocalDescriptionsSearchApiResponse {
+    #[serde(rename = "type")]
+    pub result_type: String,
+    pub results: Vec<LocationDescription>,
+    #[serde(skip)]
+    pub cache: CacheStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LocationDescription {
+    #[serde(rename = "type")]
+    pub result_type: String,
+    pub id: String,
+    pub description: Option<String>,
+}
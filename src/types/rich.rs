@@ -0,0 +1,281 @@
+use serde::{ ser::Error as _, Deserialize, Deserializer, Serialize, Serializer };
+
+use crate::brave::CacheStatus;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RichCallbackInfo {
+    #[serde(rename = "type")]
+    pub result_type: String,
+    pub hint: Option<RichCallbackHint>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RichCallbackHint {
+    pub vertical: String,
+    pub callback_key: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RichSearchApiResponse {
+    #[serde(rename = "type")]
+    pub result_type: String,
+    pub results: Vec<RichResult>,
+    #[serde(skip)]
+    pub cache: CacheStatus,
+}
+
+/// A rich result returned by `/web/rich`. Results whose vertical is unknown, or whose payload
+/// does not match the typed variant, are kept as `Unknown` rather than failing the response.
+#[derive(Debug)]
+pub enum RichResult {
+    Weather(WeatherResult),
+    Stock(StockResult),
+    Currency(CurrencyResult),
+    Cryptocurrency(CryptocurrencyResult),
+    Sports(SportsResult),
+    Calculator(CalculatorResult),
+    Definitions(DefinitionsResult),
+    UnitConversion(UnitConversionResult),
+    UnixTimestamp(UnixTimestampResult),
+    PackageTracker(PackageTrackerResult),
+    Unknown(serde_json::Value),
+}
+
+const SPORTS_VERTICALS: [&str; 6] = [
+    "american_football",
+    "baseball",
+    "basketball",
+    "cricket",
+    "football",
+    "ice_hockey",
+];
+
+impl<'de> Deserialize<'de> for RichResult {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let vertical = value
+            .get("subtype")
+            .or_else(|| value.get("type"))
+            .and_then(|vertical| vertical.as_str())
+            .unwrap_or_default()
+            .to_owned();
+
+        let result = match vertical.as_str() {
+            "weather" => typed(&value).map(RichResult::Weather),
+            "stock" => typed(&value).map(RichResult::Stock),
+            "currency" => typed(&value).map(RichResult::Currency),
+            "cryptocurrency" => typed(&value).map(RichResult::Cryptocurrency),
+            "calculator" => typed(&value).map(RichResult::Calculator),
+            "definitions" => typed(&value).map(RichResult::Definitions),
+            "unit_conversion" => typed(&value).map(RichResult::UnitConversion),
+            "unix_timestamp" => typed(&value).map(RichResult::UnixTimestamp),
+            "package_tracker" => typed(&value).map(RichResult::PackageTracker),
+            sport if SPORTS_VERTICALS.contains(&sport) => {
+                typed::<SportsResult>(&value).map(|mut result| {
+                    result.sport = vertical.clone();
+                    RichResult::Sports(result)
+                })
+            }
+            _ => None,
+        };
+        Ok(result.unwrap_or(RichResult::Unknown(value)))
+    }
+}
+
+/// Serializes typed results with the `type` and `subtype` fields they were parsed from, so they
+/// deserialize back into the same variant.
+impl Serialize for RichResult {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (subtype, result) = match self {
+            RichResult::Weather(result) => ("weather", serde_json::to_value(result)),
+            RichResult::Stock(result) => ("stock", serde_json::to_value(result)),
+            RichResult::Currency(result) => ("currency", serde_json::to_value(result)),
+            RichResult::Cryptocurrency(result) => ("cryptocurrency", serde_json::to_value(result)),
+            RichResult::Sports(result) => (result.sport.as_str(), serde_json::to_value(result)),
+            RichResult::Calculator(result) => ("calculator", serde_json::to_value(result)),
+            RichResult::Definitions(result) => ("definitions", serde_json::to_value(result)),
+            RichResult::UnitConversion(result) => ("unit_conversion", serde_json::to_value(result)),
+            RichResult::UnixTimestamp(result) => ("unix_timestamp", serde_json::to_value(result)),
+            RichResult::PackageTracker(result) => ("package_tracker", serde_json::to_value(result)),
+            RichResult::Unknown(value) => {
+                return value.serialize(serializer);
+            }
+        };
+        let mut value = result.map_err(S::Error::custom)?;
+        if let Some(fields) = value.as_object_mut() {
+            fields.insert("type".into(), "rich".into());
+            fields.insert("subtype".into(), subtype.into());
+        }
+        value.serialize(serializer)
+    }
+}
+
+fn typed<T: serde::de::DeserializeOwned>(value: &serde_json::Value) -> Option<T> {
+    serde_json::from_value(value.clone()).ok()
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WeatherResult {
+    pub location: Option<WeatherLocation>,
+    pub current_weather: Option<WeatherConditions>,
+    pub daily: Option<Vec<DailyForecast>>,
+    pub alerts: Option<Vec<WeatherAlert>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WeatherLocation {
+    pub name: String,
+    pub state: Option<String>,
+    pub country: Option<String>,
+    pub coordinates: Option<[f64; 2]>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WeatherConditions {
+    pub temp: Option<f64>,
+    pub feels_like: Option<f64>,
+    pub humidity: Option<f64>,
+    pub wind_speed: Option<f64>,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DailyForecast {
+    pub date: Option<String>,
+    pub temp_min: Option<f64>,
+    pub temp_max: Option<f64>,
+    pub precipitation_probability: Option<f64>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WeatherAlert {
+    pub event: String,
+    pub description: Option<String>,
+    pub start: Option<String>,
+    pub end: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StockResult {
+    pub symbol: String,
+    pub name: Option<String>,
+    pub exchange: Option<String>,
+    pub currency: Option<String>,
+    pub price: Option<f64>,
+    pub change: Option<f64>,
+    pub change_percent: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CurrencyResult {
+    pub from: String,
+    pub to: String,
+    pub amount: Option<f64>,
+    pub rate: Option<f64>,
+    pub converted_amount: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CryptocurrencyResult {
+    pub symbol: String,
+    pub name: Option<String>,
+    pub currency: Option<String>,
+    pub price: Option<f64>,
+    pub market_cap: Option<f64>,
+    pub change_percent_24h: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SportsResult {
+    #[serde(default)]
+    pub sport: String,
+    pub league: Option<String>,
+    pub games: Option<Vec<SportsGame>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SportsGame {
+    pub home_team: String,
+    pub away_team: String,
+    pub home_score: Option<u32>,
+    pub away_score: Option<u32>,
+    pub status: Option<String>,
+    pub start_time: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CalculatorResult {
+    pub expression: String,
+    pub answer: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DefinitionsResult {
+    pub word: String,
+    pub pronunciation: Option<String>,
+    pub definitions: Vec<Definition>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Definition {
+    pub part_of_speech: Option<String>,
+    pub definition: String,
+    pub examples: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnitConversionResult {
+    pub amount: f64,
+    pub from_unit: String,
+    pub to_unit: String,
+    pub result: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnixTimestampResult {
+    pub timestamp: i64,
+    pub utc: Option<String>,
+    pub local: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PackageTrackerResult {
+    pub tracking_number: String,
+    pub carrier: Option<String>,
+    pub status: Option<String>,
+    pub estimated_delivery: Option<String>,
+    pub events: Option<Vec<PackageEvent>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PackageEvent {
+    pub description: String,
+    pub location: Option<String>,
+    pub timestamp: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rich_result_round_trips_through_json() {
+        let results: Vec<RichResult> = serde_json::from_value(
+            serde_json::json!([
+                { "type": "rich", "subtype": "calculator", "expression": "2+2", "answer": "4" },
+                { "type": "rich", "subtype": "cricket", "league": "IPL" },
+                { "type": "rich", "subtype": "translator", "text": "hallo" }
+            ])
+        ).unwrap();
+
+        let json = serde_json::to_value(&results).unwrap();
+        let round_tripped: Vec<RichResult> = serde_json::from_value(json.clone()).unwrap();
+
+        assert_eq!(json[0]["subtype"], "calculator");
+        assert!(matches!(&round_tripped[0], RichResult::Calculator(calc) if calc.answer == "4"));
+        assert!(matches!(&round_tripped[1], RichResult::Sports(sports) if sports.sport == "cricket"));
+        assert!(matches!(&round_tripped[2], RichResult::Unknown(value) if value["text"] == "hallo"));
+    }
+}
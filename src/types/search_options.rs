@@ -0,0 +1,411 @@
+use std::{ fmt, str::FromStr };
+
+use serde::{ Serialize, Serializer };
+
+use crate::brave::{ query_encoder::serialize_comma_separated, BraveClientError };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SafeSearch {
+    Off,
+    Moderate,
+    Strict,
+}
+
+impl fmt::Display for SafeSearch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            SafeSearch::Off => "off",
+            SafeSearch::Moderate => "moderate",
+            SafeSearch::Strict => "strict",
+        };
+        f.write_str(value)
+    }
+}
+
+impl FromStr for SafeSearch {
+    type Err = BraveClientError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "off" => Ok(SafeSearch::Off),
+            "moderate" => Ok(SafeSearch::Moderate),
+            "strict" => Ok(SafeSearch::Strict),
+            _ => Err(invalid("safesearch", s, "expected off, moderate or strict")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Units {
+    Metric,
+    Imperial,
+}
+
+impl fmt::Display for Units {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Units::Metric => "metric",
+            Units::Imperial => "imperial",
+        };
+        f.write_str(value)
+    }
+}
+
+impl FromStr for Units {
+    type Err = BraveClientError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "metric" => Ok(Units::Metric),
+            "imperial" => Ok(Units::Imperial),
+            _ => Err(invalid("units", s, "expected metric or imperial")),
+        }
+    }
+}
+
+/// A section of the web search response that `result_filter` can select.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResultSection {
+    Discussions,
+    Faq,
+    Infobox,
+    News,
+    Query,
+    Summarizer,
+    Videos,
+    Web,
+    Locations,
+}
+
+impl ResultSection {
+    pub const ALL: [ResultSection; 9] = [
+        ResultSection::Discussions,
+        ResultSection::Faq,
+        ResultSection::Infobox,
+        ResultSection::News,
+        ResultSection::Query,
+        ResultSection::Summarizer,
+        ResultSection::Videos,
+        ResultSection::Web,
+        ResultSection::Locations,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResultSection::Discussions => "discussions",
+            ResultSection::Faq => "faq",
+            ResultSection::Infobox => "infobox",
+            ResultSection::News => "news",
+            ResultSection::Query => "query",
+            ResultSection::Summarizer => "summarizer",
+            ResultSection::Videos => "videos",
+            ResultSection::Web => "web",
+            ResultSection::Locations => "locations",
+        }
+    }
+
+    fn bit(&self) -> u16 {
+        1 << (*self as u16)
+    }
+}
+
+impl fmt::Display for ResultSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ResultSection {
+    type Err = BraveClientError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let section = s.trim().to_ascii_lowercase();
+        ResultSection::ALL
+            .into_iter()
+            .find(|candidate| candidate.as_str() == section)
+            .ok_or_else(|| invalid("result_filter", s, "unknown result section"))
+    }
+}
+
+/// The set of sections sent as `result_filter`, e.g. `web,news`. Sections not in the set are
+/// left out of the response.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ResultFilter {
+    bits: u16,
+}
+
+impl ResultFilter {
+    pub fn new() -> Self {
+        ResultFilter::default()
+    }
+
+    pub fn with(mut self, section: ResultSection) -> Self {
+        self.insert(section);
+        self
+    }
+
+    pub fn insert(&mut self, section: ResultSection) {
+        self.bits |= section.bit();
+    }
+
+    pub fn remove(&mut self, section: ResultSection) {
+        self.bits &= !section.bit();
+    }
+
+    pub fn contains(&self, section: ResultSection) -> bool {
+        self.bits & section.bit() != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// The selected sections, in the API's documented order.
+    pub fn iter(&self) -> impl Iterator<Item = ResultSection> + '_ {
+        ResultSection::ALL.into_iter().filter(|section| self.contains(*section))
+    }
+}
+
+impl FromIterator<ResultSection> for ResultFilter {
+    fn from_iter<I: IntoIterator<Item = ResultSection>>(sections: I) -> Self {
+        let mut filter = ResultFilter::new();
+        sections.into_iter().for_each(|section| filter.insert(section));
+        filter
+    }
+}
+
+impl<const N: usize> From<[ResultSection; N]> for ResultFilter {
+    fn from(sections: [ResultSection; N]) -> Self {
+        sections.into_iter().collect()
+    }
+}
+
+impl From<Vec<ResultSection>> for ResultFilter {
+    fn from(sections: Vec<ResultSection>) -> Self {
+        sections.into_iter().collect()
+    }
+}
+
+impl fmt::Display for ResultFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sections: Vec<&str> = self.iter().map(|section| section.as_str()).collect();
+        f.write_str(&sections.join(","))
+    }
+}
+
+impl FromStr for ResultFilter {
+    type Err = BraveClientError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .filter(|section| !section.trim().is_empty())
+            .map(str::parse)
+            .collect()
+    }
+}
+
+impl Serialize for ResultFilter {
+    /// An empty filter is left out of the query, so the API returns every section, matching
+    /// how [`Section`](crate::types::Section) treats it.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.is_empty() {
+            return serializer.serialize_none();
+        }
+        let sections: Vec<&str> = self.iter().map(|section| section.as_str()).collect();
+        serialize_comma_separated(&sections, serializer)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Freshness {
+    PastDay,
+    PastWeek,
+    PastMonth,
+    PastYear,
+    Range(Date, Date),
+}
+
+impl Freshness {
+    /// Builds a date range filter, rejecting ranges that end before they start.
+    pub fn range(start: Date, end: Date) -> Result<Self, BraveClientError> {
+        if end < start {
+            return Err(
+                invalid("freshness", &format!("{}to{}", start, end), "range ends before it starts")
+            );
+        }
+        Ok(Freshness::Range(start, end))
+    }
+}
+
+impl fmt::Display for Freshness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Freshness::PastDay => f.write_str("pd"),
+            Freshness::PastWeek => f.write_str("pw"),
+            Freshness::PastMonth => f.write_str("pm"),
+            Freshness::PastYear => f.write_str("py"),
+            Freshness::Range(start, end) => write!(f, "{}to{}", start, end),
+        }
+    }
+}
+
+impl FromStr for Freshness {
+    type Err = BraveClientError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.to_ascii_lowercase();
+        match value.as_str() {
+            "pd" => Ok(Freshness::PastDay),
+            "pw" => Ok(Freshness::PastWeek),
+            "pm" => Ok(Freshness::PastMonth),
+            "py" => Ok(Freshness::PastYear),
+            _ => {
+                let (start, end) = value.split_once("to").ok_or_else(|| {
+                    invalid("freshness", s, "expected pd, pw, pm, py or a date range")
+                })?;
+                Freshness::range(start.parse()?, end.parse()?)
+            }
+        }
+    }
+}
+
+/// A calendar date as used by freshness ranges, rendered as `YYYY-MM-DD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Self, BraveClientError> {
+        let leap_year = year.is_multiple_of(4) &&
+            (!year.is_multiple_of(100) || year.is_multiple_of(400));
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap_year => 29,
+            2 => 28,
+            _ => 0,
+        };
+        if year > 9999 || day == 0 || day > days_in_month {
+            let date = format!("{:04}-{:02}-{:02}", year, month, day);
+            return Err(invalid("date", &date, "not a calendar date"));
+        }
+        Ok(Date { year, month, day })
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = BraveClientError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split('-').collect();
+        let well_formed =
+            parts.len() == 3 &&
+            [4, 2, 2].iter().zip(&parts).all(|(len, part)| part.len() == *len) &&
+            parts.iter().all(|part| part.bytes().all(|b| b.is_ascii_digit()));
+        if !well_formed {
+            return Err(invalid("date", s, "expected YYYY-MM-DD"));
+        }
+        Date::new(
+            parts[0].parse().unwrap_or_default(),
+            parts[1].parse().unwrap_or_default(),
+            parts[2].parse().unwrap_or_default()
+        )
+    }
+}
+
+macro_rules! serialize_as_display {
+    ($($name:ident),+) => {
+        $(
+            impl Serialize for $name {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+        )+
+    };
+}
+
+serialize_as_display!(SafeSearch, Units, Freshness, Date);
+
+fn invalid(param: &str, value: &str, reason: &str) -> BraveClientError {
+    BraveClientError::InvalidParameter(format!("{} `{}`: {}", param, value, reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_freshness_range_renders_and_validates() {
+        let range = Freshness::range(
+            Date::new(2024, 2, 29).unwrap(),
+            Date::new(2024, 3, 31).unwrap()
+        ).unwrap();
+
+        assert_eq!(range.to_string(), "2024-02-29to2024-03-31");
+        assert_eq!("2024-02-29to2024-03-31".parse::<Freshness>().unwrap(), range);
+        assert_eq!("pw".parse::<Freshness>().unwrap(), Freshness::PastWeek);
+        assert_eq!("PW".parse::<Freshness>().unwrap(), Freshness::PastWeek);
+        assert_eq!("2024-02-29TO2024-03-31".parse::<Freshness>().unwrap(), range);
+        assert!(Date::new(2023, 2, 29).is_err());
+        assert!("2024-13-01".parse::<Date>().is_err());
+        assert!("2024-1-01".parse::<Date>().is_err());
+        assert!("2024-03-01to2024-02-01".parse::<Freshness>().is_err());
+        assert!("past week".parse::<Freshness>().is_err());
+    }
+
+    #[test]
+    fn test_safesearch_and_units_parse() {
+        assert_eq!("Moderate".parse::<SafeSearch>().unwrap(), SafeSearch::Moderate);
+        assert_eq!(SafeSearch::Strict.to_string(), "strict");
+        assert_eq!("imperial".parse::<Units>().unwrap(), Units::Imperial);
+        let err = "moderat".parse::<SafeSearch>().unwrap_err();
+        assert!(matches!(err, BraveClientError::InvalidParameter(msg) if msg.contains("moderat")));
+    }
+
+    #[test]
+    fn test_result_filter_is_a_comma_joined_set() {
+        let filter = ResultFilter::new()
+            .with(ResultSection::News)
+            .with(ResultSection::Web)
+            .with(ResultSection::News);
+
+        assert_eq!(filter.to_string(), "news,web");
+        assert_eq!("Web, news".parse::<ResultFilter>().unwrap(), filter);
+        assert!(filter.contains(ResultSection::Web) && !filter.contains(ResultSection::Faq));
+        assert_eq!(serde_json::to_string(&filter).unwrap(), r#"["news","web"]"#);
+        assert!("web,images".parse::<ResultFilter>().is_err());
+    }
+
+    #[test]
+    fn test_empty_result_filter_is_not_sent() {
+        let params = crate::types::query_params::WebSearchQueryParamsBuilder::default()
+            .q("rust")
+            .result_filter(ResultFilter::new())
+            .build()
+            .unwrap();
+
+        assert_eq!(crate::brave::query_encoder::encode_query(&params).unwrap(), "q=rust");
+    }
+}
@@ -0,0 +1,407 @@
+use std::{ fmt, str::FromStr };
+
+use crate::brave::BraveClientError;
+
+/// A search operator that scopes a value, rendered as `name:value`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Site,
+    FileType,
+    InTitle,
+    InBody,
+    InPage,
+    Lang,
+    Loc,
+}
+
+impl Operator {
+    const ALL: [Operator; 7] = [
+        Operator::Site,
+        Operator::FileType,
+        Operator::InTitle,
+        Operator::InBody,
+        Operator::InPage,
+        Operator::Lang,
+        Operator::Loc,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Operator::Site => "site",
+            Operator::FileType => "filetype",
+            Operator::InTitle => "intitle",
+            Operator::InBody => "inbody",
+            Operator::InPage => "inpage",
+            Operator::Lang => "lang",
+            Operator::Loc => "loc",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryNode {
+    Term(String),
+    Phrase(String),
+    Operator(Operator, String),
+    /// `+node`: results must match the node.
+    Required(Box<QueryNode>),
+    /// `-node`: results must not match the node.
+    Excluded(Box<QueryNode>),
+    /// `NOT node`
+    Not(Box<QueryNode>),
+    /// `a OR b OR c`
+    Or(Vec<QueryNode>),
+}
+
+impl fmt::Display for QueryNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryNode::Term(term) if needs_quotes(term) || looks_like_operator(term) => {
+                write_quoted(f, term)
+            }
+            QueryNode::Term(term) => f.write_str(term),
+            QueryNode::Phrase(phrase) => write_quoted(f, phrase),
+            QueryNode::Operator(operator, value) => {
+                write!(f, "{}:", operator.name())?;
+                if needs_quotes(value) { write_quoted(f, value) } else { f.write_str(value) }
+            }
+            QueryNode::Required(node) => write!(f, "+{}", node),
+            QueryNode::Excluded(node) => write!(f, "-{}", node),
+            QueryNode::Not(node) => write!(f, "NOT {}", node),
+            QueryNode::Or(nodes) => {
+                for (i, node) in nodes.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" OR ")?;
+                    }
+                    write!(f, "{}", node)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A typed search query that renders into the `q` parameter, so terms, phrases and operators
+/// are quoted consistently. Existing query strings can be parsed back with [`str::parse`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    pub nodes: Vec<QueryNode>,
+}
+
+impl SearchQuery {
+    pub fn new() -> Self {
+        SearchQuery::default()
+    }
+
+    pub fn node(mut self, node: QueryNode) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    pub fn term(self, term: impl Into<String>) -> Self {
+        self.node(QueryNode::Term(term.into()))
+    }
+
+    pub fn phrase(self, phrase: impl Into<String>) -> Self {
+        self.node(QueryNode::Phrase(phrase.into()))
+    }
+
+    pub fn operator(self, operator: Operator, value: impl Into<String>) -> Self {
+        self.node(QueryNode::Operator(operator, value.into()))
+    }
+
+    pub fn site(self, site: impl Into<String>) -> Self {
+        self.operator(Operator::Site, site)
+    }
+
+    pub fn exclude_site(self, site: impl Into<String>) -> Self {
+        self.exclude(QueryNode::Operator(Operator::Site, site.into()))
+    }
+
+    pub fn filetype(self, filetype: impl Into<String>) -> Self {
+        self.operator(Operator::FileType, filetype)
+    }
+
+    pub fn intitle(self, value: impl Into<String>) -> Self {
+        self.operator(Operator::InTitle, value)
+    }
+
+    pub fn inbody(self, value: impl Into<String>) -> Self {
+        self.operator(Operator::InBody, value)
+    }
+
+    pub fn inpage(self, value: impl Into<String>) -> Self {
+        self.operator(Operator::InPage, value)
+    }
+
+    pub fn lang(self, lang: impl Into<String>) -> Self {
+        self.operator(Operator::Lang, lang)
+    }
+
+    pub fn loc(self, loc: impl Into<String>) -> Self {
+        self.operator(Operator::Loc, loc)
+    }
+
+    pub fn require(self, node: QueryNode) -> Self {
+        self.node(QueryNode::Required(Box::new(node)))
+    }
+
+    pub fn exclude(self, node: QueryNode) -> Self {
+        self.node(QueryNode::Excluded(Box::new(node)))
+    }
+
+    pub fn not(self, node: QueryNode) -> Self {
+        self.node(QueryNode::Not(Box::new(node)))
+    }
+
+    pub fn or(self, nodes: Vec<QueryNode>) -> Self {
+        self.node(QueryNode::Or(nodes))
+    }
+}
+
+impl fmt::Display for SearchQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, node) in self.nodes.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}", node)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<SearchQuery> for String {
+    fn from(query: SearchQuery) -> Self {
+        query.to_string()
+    }
+}
+
+impl FromStr for SearchQuery {
+    type Err = BraveClientError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { input: s, pos: 0 };
+        let mut nodes: Vec<QueryNode> = vec![];
+        while let Some(token) = parser.next_token()? {
+            match token {
+                Token::Or => {
+                    let left = nodes.pop().ok_or_else(|| invalid(s, "OR without a left operand"))?;
+                    let right = parser.node(s, "OR without a right operand")?;
+                    let mut operands = match left {
+                        QueryNode::Or(operands) => operands,
+                        left => vec![left],
+                    };
+                    operands.push(right);
+                    nodes.push(QueryNode::Or(operands));
+                }
+                token => nodes.push(parser.finish_node(s, token)?),
+            }
+        }
+        Ok(SearchQuery { nodes })
+    }
+}
+
+enum Token {
+    Node(QueryNode),
+    Required,
+    Excluded,
+    Not,
+    Or,
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn node(&mut self, query: &str, missing: &str) -> Result<QueryNode, BraveClientError> {
+        match self.next_token()? {
+            Some(Token::Or) | None => Err(invalid(query, missing)),
+            Some(token) => self.finish_node(query, token),
+        }
+    }
+
+    fn finish_node(&mut self, query: &str, token: Token) -> Result<QueryNode, BraveClientError> {
+        match token {
+            Token::Node(node) => Ok(node),
+            Token::Required => {
+                Ok(QueryNode::Required(Box::new(self.node(query, "`+` without an operand")?)))
+            }
+            Token::Excluded => {
+                Ok(QueryNode::Excluded(Box::new(self.node(query, "`-` without an operand")?)))
+            }
+            Token::Not => Ok(QueryNode::Not(Box::new(self.node(query, "NOT without an operand")?))),
+            Token::Or => Err(invalid(query, "unexpected OR")),
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, BraveClientError> {
+        let rest = &self.input[self.pos..];
+        let trimmed = rest.trim_start();
+        self.pos += rest.len() - trimmed.len();
+        let Some(first) = trimmed.chars().next() else {
+            return Ok(None);
+        };
+
+        let followed_by_operand = trimmed[first.len_utf8()..].chars().next().is_some_and(|c| !c.is_whitespace());
+        if (first == '+' || first == '-') && followed_by_operand {
+            self.pos += 1;
+            return Ok(Some(if first == '+' { Token::Required } else { Token::Excluded }));
+        }
+        if first == '"' {
+            return self.quoted().map(|phrase| Some(Token::Node(QueryNode::Phrase(phrase))));
+        }
+
+        let word_len = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+        let word = &trimmed[..word_len];
+        match word {
+            "OR" => {
+                self.pos += word_len;
+                return Ok(Some(Token::Or));
+            }
+            "NOT" => {
+                self.pos += word_len;
+                return Ok(Some(Token::Not));
+            }
+            _ => {}
+        }
+        if let Some((name, value)) = word.split_once(':') {
+            let operator = Operator::ALL.into_iter().find(|op| op.name().eq_ignore_ascii_case(name));
+            if let Some(operator) = operator {
+                if value.starts_with('"') {
+                    self.pos += name.len() + 1;
+                    let value = self.quoted()?;
+                    return Ok(Some(Token::Node(QueryNode::Operator(operator, value))));
+                }
+                if !value.is_empty() {
+                    self.pos += word_len;
+                    return Ok(Some(Token::Node(QueryNode::Operator(operator, value.to_string()))));
+                }
+            }
+        }
+        self.pos += word_len;
+        Ok(Some(Token::Node(QueryNode::Term(word.to_string()))))
+    }
+
+    /// Reads a double-quoted value starting at the current position.
+    fn quoted(&mut self) -> Result<String, BraveClientError> {
+        let rest = &self.input[self.pos + 1..];
+        let end = rest.find('"').ok_or_else(|| invalid(self.input, "unterminated quote"))?;
+        self.pos += end + 2;
+        Ok(rest[..end].to_string())
+    }
+}
+
+fn needs_quotes(value: &str) -> bool {
+    value.is_empty() ||
+        value == "OR" ||
+        value == "NOT" ||
+        (value.len() > 1 && value.starts_with(['+', '-'])) ||
+        value.contains(|c: char| c.is_whitespace() || c == '"')
+}
+
+fn looks_like_operator(term: &str) -> bool {
+    term.split_once(':').is_some_and(|(name, _)| {
+        Operator::ALL.iter().any(|operator| operator.name().eq_ignore_ascii_case(name))
+    })
+}
+
+/// Quotes a value. The query syntax has no escape for `"`, so embedded quotes are dropped.
+fn write_quoted(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    write!(f, "\"{}\"", value.replace('"', ""))
+}
+
+fn invalid(query: &str, reason: &str) -> BraveClientError {
+    BraveClientError::InvalidParameter(format!("q `{}`: {}", query, reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::query_params::WebSearchQueryParamsBuilder;
+
+    #[test]
+    fn test_search_query_renders_operators() {
+        let query = SearchQuery::new()
+            .term("rust")
+            .term("async")
+            .exclude_site("reddit.com")
+            .filetype("pdf")
+            .phrase("tokio runtime")
+            .intitle("getting started")
+            .require(QueryNode::Term("tutorial".into()))
+            .or(vec![QueryNode::Term("tokio".into()), QueryNode::Term("async-std".into())])
+            .not(QueryNode::Operator(Operator::Lang, "de".into()))
+            .term("-flag")
+            .term("site:literal");
+
+        assert_eq!(
+            query.to_string(),
+            "rust async -site:reddit.com filetype:pdf \"tokio runtime\" intitle:\"getting started\" \
+            +tutorial tokio OR async-std NOT lang:de \"-flag\" \"site:literal\""
+        );
+    }
+
+    #[test]
+    fn test_search_query_renders_into_web_search_q() {
+        let query = SearchQuery::new().term("rust").exclude_site("reddit.com");
+        let params = WebSearchQueryParamsBuilder::default().q(query).build().unwrap();
+
+        assert_eq!(params.q.as_deref(), Some("rust -site:reddit.com"));
+    }
+
+    #[test]
+    fn test_search_query_round_trips() {
+        let queries = [
+            "rust async -site:reddit.com filetype:pdf \"tokio runtime\"",
+            "intitle:\"getting started\" +tutorial tokio OR async-std OR smol NOT lang:de",
+            "loc:gb inbody:borrow inpage:checker C++ 3-2 a:b",
+            "a - b",
+            "1 + 1",
+        ];
+        for query in queries {
+            let parsed: SearchQuery = query.parse().unwrap();
+            assert_eq!(parsed.to_string(), query);
+            assert_eq!(parsed.to_string().parse::<SearchQuery>().unwrap(), parsed);
+        }
+
+        let parsed: SearchQuery = "tokio OR async-std OR smol".parse().unwrap();
+        assert_eq!(
+            parsed.nodes,
+            vec![
+                QueryNode::Or(
+                    vec![
+                        QueryNode::Term("tokio".into()),
+                        QueryNode::Term("async-std".into()),
+                        QueryNode::Term("smol".into())
+                    ]
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_query_parses_multibyte_tokens() {
+        let parsed: SearchQuery = "日本 rust \"東京 タワー\" -ü".parse().unwrap();
+
+        assert_eq!(
+            parsed.nodes,
+            vec![
+                QueryNode::Term("日本".into()),
+                QueryNode::Term("rust".into()),
+                QueryNode::Phrase("東京 タワー".into()),
+                QueryNode::Excluded(Box::new(QueryNode::Term("ü".into())))
+            ]
+        );
+        assert_eq!(parsed.to_string(), "日本 rust \"東京 タワー\" -ü");
+    }
+
+    #[test]
+    fn test_search_query_rejects_malformed_queries() {
+        assert!("\"tokio runtime".parse::<SearchQuery>().is_err());
+        assert!("OR rust".parse::<SearchQuery>().is_err());
+        assert!("rust NOT".parse::<SearchQuery>().is_err());
+    }
+}
@@ -0,0 +1,108 @@
+use serde::{ Deserialize, Serialize };
+
+use crate::types::response::{ MetaUrl, Thumbnail };
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Summary {
+    #[serde(rename = "type")]
+    pub result_type: String,
+    pub status: SummaryStatus,
+    pub title: Option<String>,
+    pub summary: Option<Vec<SummaryMessage>>,
+    pub enrichments: Option<SummaryEnrichments>,
+    pub followups: Option<Vec<String>>,
+    pub entities_infos: Option<serde_json::Value>,
+}
+
+impl Summary {
+    /// Concatenates the streamed `token` messages into the plain-text summary.
+    pub fn message(&self) -> String {
+        self.summary
+            .iter()
+            .flatten()
+            .filter(|message| message.message_type == "token")
+            .filter_map(|message| message.data.as_str())
+            .collect()
+    }
+
+    pub fn entities(&self) -> &[SummaryEntity] {
+        self.enrichments
+            .as_ref()
+            .and_then(|enrichments| enrichments.entities.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// The source pages the summary was generated from.
+    pub fn citations(&self) -> &[SummaryContext] {
+        self.enrichments
+            .as_ref()
+            .and_then(|enrichments| enrichments.context.as_deref())
+            .unwrap_or_default()
+    }
+
+    pub fn followups(&self) -> &[String] {
+        self.followups.as_deref().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SummaryStatus {
+    Complete,
+    Failed,
+    #[serde(other)]
+    Pending,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummaryMessage {
+    #[serde(rename = "type")]
+    pub message_type: String,
+    pub data: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummaryEnrichments {
+    pub raw: Option<String>,
+    pub images: Option<Vec<SummaryImage>>,
+    pub qa: Option<Vec<SummaryAnswer>>,
+    pub entities: Option<Vec<SummaryEntity>>,
+    pub context: Option<Vec<SummaryContext>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummaryImage {
+    pub src: Option<String>,
+    pub alt: Option<String>,
+    pub thumbnail: Option<Thumbnail>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummaryAnswer {
+    pub answer: String,
+    pub score: Option<f32>,
+    pub highlight: Option<TextLocation>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummaryEntity {
+    pub uuid: Option<String>,
+    pub name: String,
+    pub url: Option<String>,
+    pub text: Option<String>,
+    pub images: Option<Vec<SummaryImage>>,
+    pub highlight: Option<Vec<TextLocation>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummaryContext {
+    pub title: String,
+    pub url: String,
+    pub meta_url: Option<MetaUrl>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TextLocation {
+    pub start: u32,
+    pub end: u32,
+}